@@ -0,0 +1,151 @@
+#![cfg(feature = "mysql")]
+
+use itertools::Itertools;
+use syn::{parse_quote, Expr, Ident};
+
+pub fn db_ident() -> Ident {
+    parse_quote!(MySql)
+}
+
+pub fn db_sqlx_path() -> Expr {
+    parse_quote!(sqlx::mysql::MySql)
+}
+
+pub fn qmarks(nb: usize) -> String {
+    (0..nb).map(|_| "?").join(",")
+}
+
+pub fn qmarks_with_col(_: usize, cols: &[&str]) -> String {
+    cols.iter().map(|c| format!("{c}=?")).join(",")
+}
+
+/// Value proposed for insertion in an upsert.
+pub fn excluded_column(column: &str) -> String {
+    format!("VALUES({column})")
+}
+
+pub fn db_query_result_path() -> Expr {
+    parse_quote!(sqlx::mysql::MySqlQueryResult)
+}
+
+/// Highest number of bind parameters in a query.
+pub const MAX_BIND_PARAMS: usize = 65535;
+
+/// No `RETURNING`: inserted rows can't be fetched back.
+pub const SUPPORTS_RETURNING: bool = false;
+
+/// Runtime lists of `in` are bound as a json array, not as a native array.
+pub const LIST_AS_JSON: bool = true;
+
+/// `column IN` a runtime list.
+pub fn in_list(column: &str, list: &str) -> String {
+    format!("JSON_CONTAINS({list}, JSON_ARRAY({column}))")
+}
+
+/// `column op ANY/ALL` a subquery, parentheses included.
+pub fn quantified_subquery(column: &str, op: &str, all: bool, subquery: &str) -> Option<String> {
+    let quantifier = if all { "ALL" } else { "ANY" };
+    Some(format!("{column} {op} {quantifier}{subquery}"))
+}
+
+/// `column op ANY/ALL` a runtime list: only `== any` and `!= all` can be rewritten.
+pub fn quantified_list(column: &str, op: &str, all: bool, list: &str) -> Option<String> {
+    match (op, all) {
+        ("=", false) => Some(in_list(column, list)),
+        ("<>", true) => Some(format!("NOT {}", in_list(column, list))),
+        _ => None,
+    }
+}
+
+/// Operators written differently with this database.
+pub fn rewrite_operation(lhs: &str, sign: &str, rhs: &str) -> Option<String> {
+    match sign {
+        "ILIKE" => Some(format!("LOWER({lhs}) LIKE LOWER({rhs})")),
+        "NOT ILIKE" => Some(format!("LOWER({lhs}) NOT LIKE LOWER({rhs})")),
+        "IS NOT DISTINCT FROM" => Some(format!("{lhs} <=> {rhs}")),
+        "IS DISTINCT FROM" => Some(format!("NOT ({lhs} <=> {rhs})")),
+        _ => None,
+    }
+}
+
+/// String concatenation.
+pub fn concat(left: &str, right: &str) -> String {
+    format!("CONCAT({left}, {right})")
+}
+
+/// Number of characters.
+pub fn length(column: &str) -> String {
+    format!("CHAR_LENGTH({column})")
+}
+
+/// Wildcard of a `pattern_match` pattern.
+pub const PATTERN_WILDCARD: &str = "%";
+
+/// Characters matched as themselves in a `pattern_match` pattern, with their escaped form.
+/// Not a backslash, which is also an escape character in MySQL strings.
+pub const PATTERN_ESCAPES: &[(&str, &str)] = &[("!", "!!"), ("%", "!%"), ("_", "!_")];
+
+/// Case sensitive match of a pattern: LIKE ignores case with the default collations.
+pub fn pattern_match(column: &str, pattern: &str) -> String {
+    format!("{column} LIKE CAST({pattern} AS BINARY) ESCAPE '!'")
+}
+
+#[cfg(test)]
+mod test_database_sqlite {
+
+    test_qmarks!(1 "?" );
+    test_qmarks!(2 "?,?");
+    test_qmarks!(0 "" );
+
+    test_qmarks_with_col!("bla","bli"; "bla=?,bli=?");
+    test_qmarks_with_col!("bla"; "bla=?");
+    test_qmarks_with_col!(; "");
+
+    #[test]
+    fn test_in_list() {
+        assert_eq!(
+            super::in_list("a.id", "?"),
+            "JSON_CONTAINS(?, JSON_ARRAY(a.id))"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_operation() {
+        assert_eq!(
+            super::rewrite_operation("a", "IS DISTINCT FROM", "?").unwrap(),
+            "NOT (a <=> ?)"
+        );
+        assert_eq!(
+            super::rewrite_operation("a", "ILIKE", "?").unwrap(),
+            "LOWER(a) LIKE LOWER(?)"
+        );
+        assert!(super::rewrite_operation("a", "REGEXP", "?").is_none());
+    }
+
+    #[test]
+    fn test_quantified() {
+        assert_eq!(
+            super::quantified_subquery("a.id", ">", true, "(SELECT 1)").unwrap(),
+            "a.id > ALL(SELECT 1)"
+        );
+        assert_eq!(
+            super::quantified_list("a.id", "<>", true, "?").unwrap(),
+            "NOT JSON_CONTAINS(?, JSON_ARRAY(a.id))"
+        );
+        assert!(super::quantified_list("a.id", ">", true, "?").is_none());
+    }
+
+    #[test]
+    fn test_concat_length() {
+        assert_eq!(super::concat("a.b", "'%'"), "CONCAT(a.b, '%')");
+        assert_eq!(super::length("a.b"), "CHAR_LENGTH(a.b)");
+    }
+
+    #[test]
+    fn test_pattern_match() {
+        assert_eq!(
+            super::pattern_match("a.b", "?"),
+            "a.b LIKE CAST(? AS BINARY) ESCAPE '!'"
+        );
+    }
+}
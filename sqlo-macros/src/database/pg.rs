@@ -0,0 +1,146 @@
+#![cfg(feature = "postgres")]
+
+use itertools::Itertools;
+use syn::{parse_quote, Expr, Ident};
+
+pub fn db_ident() -> Ident {
+    parse_quote!(Postgres)
+}
+
+pub fn db_sqlx_path() -> Expr {
+    parse_quote!(sqlx::postgres::Postgres)
+}
+
+pub fn qmarks(nb: usize) -> String {
+    (1..=nb).map(|x| format!("${x}")).join(",")
+}
+
+pub fn qmarks_with_col(skip: usize, cols: &[&str]) -> String {
+    cols.iter()
+        .enumerate()
+        .map(|(x, c)| {
+            let z = x + 1 + skip;
+            format!("{c}=${z}")
+        })
+        .join(",")
+}
+
+/// Value proposed for insertion in an upsert.
+pub fn excluded_column(column: &str) -> String {
+    format!("excluded.{column}")
+}
+
+pub fn db_query_result_path() -> Expr {
+    parse_quote!(sqlx::postgres::PgQueryResult)
+}
+
+/// Highest number of bind parameters in a query.
+pub const MAX_BIND_PARAMS: usize = 65535;
+
+/// `RETURNING` is supported.
+pub const SUPPORTS_RETURNING: bool = true;
+
+/// Runtime lists of `in` are bound as a json array, not as a native array.
+pub const LIST_AS_JSON: bool = false;
+
+/// `column IN` a runtime list.
+pub fn in_list(column: &str, list: &str) -> String {
+    format!("{column} = ANY({list})")
+}
+
+/// `column op ANY/ALL` a subquery, parentheses included.
+pub fn quantified_subquery(column: &str, op: &str, all: bool, subquery: &str) -> Option<String> {
+    Some(format!("{column} {op} {}{subquery}", quantifier(all)))
+}
+
+/// `column op ANY/ALL` a runtime list.
+pub fn quantified_list(column: &str, op: &str, all: bool, list: &str) -> Option<String> {
+    Some(format!("{column} {op} {}({list})", quantifier(all)))
+}
+
+/// Operators written differently with this database.
+pub fn rewrite_operation(lhs: &str, sign: &str, rhs: &str) -> Option<String> {
+    match sign {
+        "REGEXP" => Some(format!("{lhs} ~ {rhs}")),
+        "NOT REGEXP" => Some(format!("{lhs} !~ {rhs}")),
+        _ => None,
+    }
+}
+
+fn quantifier(all: bool) -> &'static str {
+    if all {
+        "ALL"
+    } else {
+        "ANY"
+    }
+}
+
+/// String concatenation.
+pub fn concat(left: &str, right: &str) -> String {
+    format!("CAST({left} AS TEXT) || {right}")
+}
+
+/// Number of characters.
+pub fn length(column: &str) -> String {
+    format!("LENGTH({column})")
+}
+
+/// Wildcard of a `pattern_match` pattern.
+pub const PATTERN_WILDCARD: &str = "%";
+
+/// Characters matched as themselves in a `pattern_match` pattern, with their escaped form.
+pub const PATTERN_ESCAPES: &[(&str, &str)] = &[("!", "!!"), ("%", "!%"), ("_", "!_")];
+
+/// Case sensitive match of a pattern.
+pub fn pattern_match(column: &str, pattern: &str) -> String {
+    format!("{column} LIKE {pattern} ESCAPE '!'")
+}
+
+#[cfg(test)]
+mod test_database_pg {
+
+    test_qmarks!(1 "$1" );
+    test_qmarks!(2 "$1,$2");
+    test_qmarks!(0 "" );
+
+    test_qmarks_with_col!("bla","bli"; "bla=$1,bli=$2");
+    test_qmarks_with_col!("bla"; "bla=$1");
+    test_qmarks_with_col!(; "");
+
+    #[test]
+    fn test_in_list() {
+        assert_eq!(super::in_list("a.id", "$1"), "a.id = ANY($1)");
+    }
+
+    #[test]
+    fn test_rewrite_operation() {
+        assert_eq!(
+            super::rewrite_operation("a", "NOT REGEXP", "$1").unwrap(),
+            "a !~ $1"
+        );
+        assert!(super::rewrite_operation("a", "ILIKE", "$1").is_none());
+    }
+
+    #[test]
+    fn test_quantified() {
+        assert_eq!(
+            super::quantified_subquery("a.id", ">", true, "(SELECT 1)").unwrap(),
+            "a.id > ALL(SELECT 1)"
+        );
+        assert_eq!(
+            super::quantified_list("a.id", "<", false, "$1").unwrap(),
+            "a.id < ANY($1)"
+        );
+    }
+
+    #[test]
+    fn test_concat_length() {
+        assert_eq!(super::concat("a.b", "'%'"), "CAST(a.b AS TEXT) || '%'");
+        assert_eq!(super::length("a.b"), "LENGTH(a.b)");
+    }
+
+    #[test]
+    fn test_pattern_match() {
+        assert_eq!(super::pattern_match("a.b", "$1"), "a.b LIKE $1 ESCAPE '!'");
+    }
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "sqlite")]
+
+use itertools::Itertools;
+use syn::{parse_quote, Expr, Ident};
+
+pub fn db_ident() -> Ident {
+    parse_quote!(Sqlite)
+}
+
+pub fn db_sqlx_path() -> Expr {
+    parse_quote!(sqlx::sqlite::Sqlite)
+}
+
+pub fn qmarks(nb: usize) -> String {
+    (0..nb).map(|_| "?").join(",")
+}
+
+pub fn qmarks_with_col(_: usize, cols: &[&str]) -> String {
+    cols.iter().map(|c| format!("{c}=?")).join(",")
+}
+
+/// Value proposed for insertion in an upsert.
+pub fn excluded_column(column: &str) -> String {
+    format!("excluded.{column}")
+}
+
+pub fn db_query_result_path() -> Expr {
+    parse_quote!(sqlx::sqlite::SqliteQueryResult)
+}
+
+/// Highest number of bind parameters in a query (lowest default among sqlite versions).
+pub const MAX_BIND_PARAMS: usize = 999;
+
+/// `RETURNING` is supported.
+pub const SUPPORTS_RETURNING: bool = true;
+
+/// Runtime lists of `in` are bound as a json array, not as a native array.
+pub const LIST_AS_JSON: bool = true;
+
+/// `column IN` a runtime list.
+pub fn in_list(column: &str, list: &str) -> String {
+    format!("{column} IN (SELECT value FROM json_each({list}))")
+}
+
+/// `column op ANY/ALL` a subquery, parentheses included.
+/// Sqlite has no ANY/ALL: rewritten with `IN` or `EXISTS`.
+/// With ALL, a NULL comparison is not a match, like the standard one dropping the row.
+pub fn quantified_subquery(column: &str, op: &str, all: bool, subquery: &str) -> Option<String> {
+    Some(match (op, all) {
+        ("=", false) => format!("{column} IN {subquery}"),
+        ("<>", true) => format!("{column} NOT IN {subquery}"),
+        (_, false) => format!(
+            "EXISTS (WITH sqlo_q(sqlo_v) AS {subquery} SELECT 1 FROM sqlo_q WHERE {column} {op} sqlo_v)"
+        ),
+        (_, true) => format!(
+            "NOT EXISTS (WITH sqlo_q(sqlo_v) AS {subquery} SELECT 1 FROM sqlo_q WHERE ({column} {op} sqlo_v) IS NOT 1)"
+        ),
+    })
+}
+
+/// `column op ANY/ALL` a runtime list, rewritten like a subquery.
+pub fn quantified_list(column: &str, op: &str, all: bool, list: &str) -> Option<String> {
+    Some(match (op, all) {
+        ("=", false) => in_list(column, list),
+        ("<>", true) => format!("NOT {}", in_list(column, list)),
+        (_, false) => {
+            format!("EXISTS (SELECT 1 FROM json_each({list}) WHERE {column} {op} value)")
+        }
+        (_, true) => {
+            format!(
+                "NOT EXISTS (SELECT 1 FROM json_each({list}) WHERE ({column} {op} value) IS NOT 1)"
+            )
+        }
+    })
+}
+
+/// Operators written differently with this database.
+/// REGEXP needs a `regexp` function to be registered.
+pub fn rewrite_operation(lhs: &str, sign: &str, rhs: &str) -> Option<String> {
+    match sign {
+        "ILIKE" => Some(format!("LOWER({lhs}) LIKE LOWER({rhs})")),
+        "NOT ILIKE" => Some(format!("LOWER({lhs}) NOT LIKE LOWER({rhs})")),
+        "IS NOT DISTINCT FROM" => Some(format!("{lhs} IS {rhs}")),
+        "IS DISTINCT FROM" => Some(format!("{lhs} IS NOT {rhs}")),
+        _ => None,
+    }
+}
+
+/// String concatenation.
+pub fn concat(left: &str, right: &str) -> String {
+    format!("{left} || {right}")
+}
+
+/// Number of characters.
+pub fn length(column: &str) -> String {
+    format!("LENGTH({column})")
+}
+
+/// Wildcard of a `pattern_match` pattern.
+pub const PATTERN_WILDCARD: &str = "*";
+
+/// Characters matched as themselves in a `pattern_match` pattern, with their escaped form.
+pub const PATTERN_ESCAPES: &[(&str, &str)] = &[("[", "[[]"), ("*", "[*]"), ("?", "[?]")];
+
+/// Case sensitive match of a pattern: GLOB since LIKE ignores ASCII case.
+pub fn pattern_match(column: &str, pattern: &str) -> String {
+    format!("{column} GLOB {pattern}")
+}
+
+#[cfg(test)]
+mod test_database_sqlite {
+
+    test_qmarks!(1 "?" );
+    test_qmarks!(2 "?,?");
+    test_qmarks!(0 "" );
+
+    test_qmarks_with_col!("bla","bli"; "bla=?,bli=?");
+    test_qmarks_with_col!("bla"; "bla=?");
+    test_qmarks_with_col!(; "");
+
+    #[test]
+    fn test_in_list() {
+        assert_eq!(
+            super::in_list("a.id", "?"),
+            "a.id IN (SELECT value FROM json_each(?))"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_operation() {
+        assert_eq!(
+            super::rewrite_operation("a", "IS DISTINCT FROM", "?").unwrap(),
+            "a IS NOT ?"
+        );
+        assert_eq!(
+            super::rewrite_operation("a", "NOT ILIKE", "?").unwrap(),
+            "LOWER(a) NOT LIKE LOWER(?)"
+        );
+        assert!(super::rewrite_operation("a", "REGEXP", "?").is_none());
+    }
+
+    #[test]
+    fn test_quantified() {
+        assert_eq!(
+            super::quantified_subquery("a.id", "=", false, "(SELECT 1)").unwrap(),
+            "a.id IN (SELECT 1)"
+        );
+        assert_eq!(
+            super::quantified_subquery("a.id", ">", true, "(SELECT 1)").unwrap(),
+            "NOT EXISTS (WITH sqlo_q(sqlo_v) AS (SELECT 1) SELECT 1 FROM sqlo_q WHERE (a.id > sqlo_v) IS NOT 1)"
+        );
+        assert_eq!(
+            super::quantified_list("a.id", "<", false, "?").unwrap(),
+            "EXISTS (SELECT 1 FROM json_each(?) WHERE a.id < value)"
+        );
+        assert_eq!(
+            super::quantified_list("a.id", "<", true, "?").unwrap(),
+            "NOT EXISTS (SELECT 1 FROM json_each(?) WHERE (a.id < value) IS NOT 1)"
+        );
+    }
+
+    #[test]
+    fn test_concat_length() {
+        assert_eq!(super::concat("a.b", "'%'"), "a.b || '%'");
+        assert_eq!(super::length("a.b"), "LENGTH(a.b)");
+    }
+
+    #[test]
+    fn test_pattern_match() {
+        assert_eq!(super::pattern_match("a.b", "?"), "a.b GLOB ?");
+    }
+}
@@ -16,6 +16,7 @@ pub struct FieldParser {
     insert_fn: Option<syn::ExprPath>,
     pub fk: Option<IdentString>,
     pub related: Option<IdentString>,
+    pub parent: Option<IdentString>,
 }
 
 impl FieldParser {
@@ -80,6 +81,17 @@ impl FieldParser {
             Ok(self.related.clone())
         }
     }
+
+    pub fn parent(&self) -> syn::Result<Option<IdentString>> {
+        if self.parent.is_some() && self.fk.is_none() {
+            Err(syn::Error::new_spanned(
+                self.parent.clone(),
+                "`fk` has to be set",
+            ))
+        } else {
+            Ok(self.parent.clone())
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -97,6 +109,8 @@ pub struct Field {
     pub fk: Option<IdentString>,
     #[serde(with = "OptionIdentStringSer")]
     pub related: Option<IdentString>,
+    #[serde(default, with = "OptionIdentStringSer")]
+    pub parent: Option<IdentString>,
 }
 
 impl TryFrom<FieldParser> for Field {
@@ -111,6 +125,7 @@ impl TryFrom<FieldParser> for Field {
             primary_key: fp.primary_key,
             fk: fp.fk()?,
             related: fp.related()?,
+            parent: fp.parent()?,
             insert_fn: fp.insert_fn,
         })
     }
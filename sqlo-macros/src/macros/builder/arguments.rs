@@ -28,6 +28,23 @@ impl Arguments {
     // Return arguments as a sequence matching query pattern
     #[cfg(not(feature = "postgres"))]
     pub fn as_result(&self, query: &str) -> Vec<&Expr> {
+        self.as_sequence(query)
+    }
+
+    // Insert arguments of a query built by another generator, return the query with placeholders renumbered.
+    pub fn merge(&mut self, query: &str, other: &Arguments) -> String {
+        regex_macro::regex!(r"\$(\d+)")
+            .replace_all(query, |caps: &regex::Captures| {
+                let idx = caps[1]
+                    .parse::<usize>()
+                    .expect("Sqlo Internal Error: failed parsing usize argument");
+                format!("${}", self.insert(&other.0[idx - 1]))
+            })
+            .to_string()
+    }
+
+    // Return arguments in placeholder order, doubles included.
+    pub fn as_sequence(&self, query: &str) -> Vec<&Expr> {
         get_indexes_form_query(query)
             .iter()
             .map(|idx| &self.0[idx - 1])
@@ -51,6 +51,13 @@ impl ColumnToSql for &IdentString {
                     .tables
                     .alias_dot_column(&ctx.main_sqlo.ident, self)?
                     .into()),
+                // existing row in upsert, unqualified is ambiguous with postgres
+                Mode::Insert if ctx.context.contains(&Context::OnConflict) => Ok(format!(
+                    "{}.{}",
+                    ctx.tables.tablename(&ctx.main_sqlo.ident)?,
+                    ctx.tables.column(&ctx.main_sqlo.ident, self)?
+                )
+                .into()),
                 _ => Ok(ctx.tables.column(&ctx.main_sqlo.ident, self)?.into()),
             }
         }
@@ -0,0 +1,150 @@
+use darling::util::IdentString;
+use itertools::Itertools;
+
+use crate::{
+    error::SqloError,
+    macros::{
+        kw, scoped_sqlos, ColExpr, Column, ColumnCast, ColumnToSql, Context, Fragment, Generator,
+        Mode, QueryParser, SelectParser, TableAliases,
+    },
+    sqlos::Sqlos,
+};
+
+/// Set operator between two select queries.
+#[derive(Debug, Clone, Copy)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl SetOperator {
+    pub fn next_is_set_operator(input: &syn::parse::ParseStream) -> bool {
+        input.peek(kw::union) || input.peek(kw::intersect) || input.peek(kw::except)
+    }
+}
+
+impl syn::parse::Parse for SetOperator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::union) {
+            input.parse::<kw::union>()?;
+            if input.peek(kw::all) {
+                input.parse::<kw::all>()?;
+                Ok(SetOperator::UnionAll)
+            } else {
+                Ok(SetOperator::Union)
+            }
+        } else if input.peek(kw::intersect) {
+            input.parse::<kw::intersect>()?;
+            Ok(SetOperator::Intersect)
+        } else if input.peek(kw::except) {
+            input.parse::<kw::except>()?;
+            Ok(SetOperator::Except)
+        } else {
+            Err(input.error("expect `union`, `intersect` or `except`"))
+        }
+    }
+}
+
+impl std::fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetOperator::Union => write!(f, "UNION"),
+            SetOperator::UnionAll => write!(f, "UNION ALL"),
+            SetOperator::Intersect => write!(f, "INTERSECT"),
+            SetOperator::Except => write!(f, "EXCEPT"),
+        }
+    }
+}
+
+/// Query combined to the main one: `House where width > 100 union House where zipcode == 1`
+#[derive(Debug, Clone)]
+pub struct Compound {
+    pub operator: SetOperator,
+    pub query: Box<SelectParser>,
+}
+
+impl Compound {
+    /// Output column names of the main query, checked against the combined one.
+    pub fn columns<T: QueryParser>(
+        &self,
+        parsed: &T,
+        sqlos: &Sqlos,
+    ) -> Result<Vec<IdentString>, SqloError> {
+        let main = output_columns(parsed, sqlos)?;
+        let other_sqlos = scoped_sqlos(self.query.as_ref(), sqlos)?;
+        let other = output_columns(self.query.as_ref(), &other_sqlos)?;
+        if main != other {
+            return Err(SqloError::new_spanned(
+                self.query.entity(),
+                format!(
+                    "both sides of `{}` must select the same columns: expected `{}`, found `{}`",
+                    self.operator.to_string().to_lowercase(),
+                    main.iter().join(", "),
+                    other.iter().join(", ")
+                ),
+            ));
+        }
+        // whole rows are the fields of a single sqlo struct
+        if parsed.columns().is_empty() {
+            let main = Generator::get_main_sqlo(parsed, sqlos)?;
+            let other = Generator::get_main_sqlo(self.query.as_ref(), &other_sqlos)?;
+            if main.ident != other.ident {
+                return Err(SqloError::new_spanned(
+                    self.query.entity(),
+                    format!(
+                        "both sides of `{}` must select rows of `{}`",
+                        self.operator.to_string().to_lowercase(),
+                        main.ident
+                    ),
+                ));
+            }
+        }
+        Ok(main)
+    }
+}
+
+impl ColumnToSql for Compound {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        // the combined query has its own scope, its column names come from the main query
+        let sqlos = scoped_sqlos(self.query.as_ref(), ctx.sqlos)?;
+        let mut generator = Generator::from_sqlo_query_parse(
+            Mode::Select,
+            self.query.as_ref().clone(),
+            &sqlos,
+            true,
+            TableAliases::new(&sqlos),
+        )?;
+        generator.context.push(Context::Compound);
+        let qr: Fragment = generator.try_into()?;
+        let query = ctx.arguments.merge(&qr.query, &qr.params);
+        Ok(format!(" {} {query}", self.operator).into())
+    }
+}
+
+// name of every selected column, as sqlx will name the field
+fn output_columns<T: QueryParser>(
+    parsed: &T,
+    sqlos: &Sqlos,
+) -> Result<Vec<IdentString>, SqloError> {
+    if parsed.columns().is_empty() {
+        let sqlo = Generator::get_main_sqlo(parsed, sqlos)?;
+        return Ok(sqlo.fields.iter().map(|f| f.ident.clone()).collect());
+    }
+    parsed
+        .columns()
+        .iter()
+        .map(|column| match column {
+            Column::Mono(ColExpr::Ident(ident)) => Ok(ident.clone()),
+            Column::Mono(ColExpr::Field(field)) => Ok(field.member().clone()),
+            Column::Cast(ColumnCast { alias, .. }) => alias
+                .name()
+                .ok_or_else(|| SqloError::new_spanned(alias, "invalid alias format")),
+            Column::Mono(expr) => Err(SqloError::new_spanned(
+                expr,
+                "column of a combined query must be a field or be aliased with `as`",
+            )),
+        })
+        .collect()
+}
@@ -3,6 +3,7 @@ pub enum Context {
     Call,
     Cast,
     SubQuery,
+    Compound,
     Where,
     Field,
     OrderBy,
@@ -10,4 +11,5 @@ pub enum Context {
     Paren,
     Unary,
     Assign,
+    OnConflict,
 }
@@ -0,0 +1,47 @@
+use crate::macros::Fetch;
+use darling::util::IdentString;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+
+pub fn expand_delete(
+    fetch: Fetch,
+    ident: &IdentString,
+    query: String,
+    arguments: &[&Expr],
+    move_instance: TokenStream,
+    lists: TokenStream,
+) -> TokenStream {
+    match fetch {
+        Fetch::Stream => {
+            quote! {
+                |pool|{
+                    sqlx::query_as!(#ident,#query, #(#arguments),*).#fetch(pool)
+                }
+            }
+        }
+        Fetch::None => {
+            quote::quote! {
+
+                |pool|{
+                    async move {
+                    #move_instance
+                    #lists
+                    sqlx::query!(#query, #(#arguments),*).#fetch(pool).await
+                    }
+                }
+            }
+        }
+        _ => {
+            quote::quote! {
+                |pool|{
+                    async move {
+                    #move_instance
+                    #lists
+                    sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool).await
+                    }
+                }
+            }
+        }
+    }
+}
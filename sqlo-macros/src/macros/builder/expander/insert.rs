@@ -0,0 +1,213 @@
+use darling::util::IdentString;
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Expr, ExprPath};
+
+use crate::{
+    database::{db_query_result_path, db_sqlx_path, MAX_BIND_PARAMS},
+    field::Field,
+    macros::Fetch,
+    sqlo::Sqlo,
+    utils::INSERT_FN_FLAG,
+};
+
+use super::{select::decode_row, WhichMacro};
+
+pub fn expand_insert(
+    fetch: Fetch,
+    ident: &IdentString,
+    query: String,
+    arguments: &[&Expr],
+    main_sqlo: &Sqlo,
+) -> TokenStream {
+    match fetch {
+        Fetch::Stream => {
+            unimplemented!("fetch not supported with insert!")
+        }
+        Fetch::None => {
+            if query.contains(INSERT_FN_FLAG) {
+                return expand_with_insert_fn(
+                    fetch,
+                    ident,
+                    query,
+                    arguments,
+                    main_sqlo,
+                    WhichMacro::Query,
+                );
+            }
+            quote::quote! {
+                |pool|{
+                    sqlx::query!(#query, #(#arguments),*).#fetch(pool)
+                }
+            }
+        }
+        _ => {
+            if query.contains(INSERT_FN_FLAG) {
+                return expand_with_insert_fn(
+                    fetch,
+                    ident,
+                    query,
+                    arguments,
+                    main_sqlo,
+                    WhichMacro::QueryAs,
+                );
+            }
+            quote::quote! {
+                |pool|{
+                    sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool)
+                }
+            }
+        }
+    }
+}
+
+fn expand_with_insert_fn(
+    fetch: Fetch,
+    ident: &IdentString,
+    query: String,
+    arguments: &[&Expr],
+    main_sqlo: &Sqlo,
+    which_macro: WhichMacro,
+) -> TokenStream {
+    let Field {
+        insert_fn, column, ..
+    } = main_sqlo
+        .single_pk_field()
+        .expect("Sqlo Internal Error: insert_fn needs a single primary key");
+    let insert_fn = insert_fn.clone().unwrap();
+    // one call of insert_fn per inserted row
+    let mut insert_fn_toks = TokenStream::new();
+    let arguments: Vec<Expr> = arguments
+        .iter()
+        .cloned()
+        .map(|m| match m {
+            Expr::Path(ExprPath { path, .. }) => {
+                if let Some(ident) = path.get_ident() {
+                    if let Some(suffix) = ident.to_string().strip_prefix(INSERT_FN_FLAG) {
+                        let var = format_ident!("insert_fn{}", suffix);
+                        insert_fn_toks.extend(quote! {let #var = #insert_fn();});
+                        return syn::parse_quote! {#var};
+                    }
+                }
+                m.clone()
+            }
+            _ => m.clone(),
+        })
+        .collect();
+    let query = query.replace(INSERT_FN_FLAG, column);
+    match which_macro {
+        WhichMacro::QueryAs => quote::quote! {
+            |pool|{
+                async move {
+                    #insert_fn_toks
+                    sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool).await
+                }
+            }
+        },
+        WhichMacro::Query => quote::quote! {
+            |pool|{
+                async move {
+                    #insert_fn_toks
+                    sqlx::query!(#query, #(#arguments),*).#fetch(pool).await
+                }
+            }
+        },
+    }
+}
+
+// Instances are only known at runtime so the query is built with sqlx::QueryBuilder.
+// Rows are sent by chunks to stay under the bind parameters limit of the database,
+// all on the same connection so a transaction can be given.
+pub fn expand_insert_instances(
+    fetch: Fetch,
+    instances: &Expr,
+    main_sqlo: &Sqlo,
+    on_conflict: &str,
+    on_conflict_args: &[&Expr],
+) -> TokenStream {
+    let Sqlo {
+        ident,
+        tablename,
+        fields,
+        ..
+    } = main_sqlo;
+    let db_path = db_sqlx_path();
+    let columns = fields.iter().map(|f| f.column.as_str()).join(",");
+    let query = format!("INSERT INTO {tablename} ({columns}) ");
+    let chunk_size = (MAX_BIND_PARAMS.saturating_sub(on_conflict_args.len()) / fields.len()).max(1);
+    // like with rows, insert_fn gives the primary key of every instance
+    let binds = fields.iter().map(|f| match &f.insert_fn {
+        Some(insert_fn) => quote! {row.push_bind(#insert_fn());},
+        None => {
+            let field = &f.ident;
+            quote! {row.push_bind(instance.#field.clone());}
+        }
+    });
+    // on_conflict parameters are evaluated before the future and bound at their placeholder
+    let conflict_pieces = regex_macro::regex!(r"\$\d+")
+        .split(on_conflict)
+        .collect::<Vec<_>>();
+    let conflict_last = conflict_pieces.last().copied().unwrap_or_default();
+    let conflict_pieces = &conflict_pieces[..conflict_pieces.len().saturating_sub(1)];
+    let conflict_vars = (0..on_conflict_args.len())
+        .map(|i| format_ident!("on_conflict_arg_{}", i))
+        .collect::<Vec<_>>();
+    let conflict_vars_toks = quote! {
+        #(let #conflict_vars = ::std::clone::Clone::clone(&(#on_conflict_args));)*
+    };
+    let push_values = quote! {
+        let mut query_builder = sqlx::QueryBuilder::<#db_path>::new(#query);
+        query_builder.push_values(instances.by_ref().take(#chunk_size), |mut row, instance| {
+            #(#binds)*
+        });
+        #(
+            query_builder.push(#conflict_pieces);
+            query_builder.push_bind(::std::clone::Clone::clone(&#conflict_vars));
+        )*
+        query_builder.push(#conflict_last);
+    };
+    match fetch {
+        Fetch::All => {
+            let returning = format!(" RETURNING {columns}");
+            let decode = decode_row(main_sqlo);
+            quote! {
+                |pool| {
+                    let mut instances = ::std::iter::IntoIterator::into_iter(#instances).peekable();
+                    #conflict_vars_toks
+                    async move {
+                        let mut conn = ::sqlo::acquire(pool).await?;
+                        let mut inserted: Vec<#ident> = Vec::new();
+                        while instances.peek().is_some() {
+                            #push_values
+                            query_builder.push(#returning);
+                            for row in query_builder.build().fetch_all(&mut *conn).await?.iter() {
+                                inserted.push(#decode);
+                            }
+                        }
+                        Ok::<_, sqlx::Error>(inserted)
+                    }
+                }
+            }
+        }
+        _ => {
+            let query_result = db_query_result_path();
+            quote! {
+                |pool| {
+                    let mut instances = ::std::iter::IntoIterator::into_iter(#instances).peekable();
+                    #conflict_vars_toks
+                    async move {
+                        let mut conn = ::sqlo::acquire(pool).await?;
+                        let mut result = <#query_result as Default>::default();
+                        while instances.peek().is_some() {
+                            #push_values
+                            let chunk = query_builder.build().execute(&mut *conn).await?;
+                            ::std::iter::Extend::extend(&mut result, Some(chunk));
+                        }
+                        Ok::<_, sqlx::Error>(result)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+mod delete;
+mod insert;
+mod select;
+mod update;
+mod which_macro;
+
+pub use delete::expand_delete;
+pub use insert::{expand_insert, expand_insert_instances};
+pub use select::{decode_row, expand_select, expand_select_dynamic, expand_select_include};
+pub use update::expand_update;
+pub use which_macro::WhichMacro;
+
+/// Json runtime lists of `in`, converted to owned strings before the query borrows them.
+pub fn list_bindings(lists: &[(syn::Ident, syn::Expr)]) -> proc_macro2::TokenStream {
+    let bindings = lists.iter().map(|(binding, list)| {
+        quote::quote! {let #binding = ::sqlo::json_array(&(#list));}
+    });
+    quote::quote! {#(#bindings)*}
+}
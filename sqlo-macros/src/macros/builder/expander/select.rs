@@ -0,0 +1,243 @@
+use crate::{
+    database::{db_sqlx_path, MAX_BIND_PARAMS},
+    field::Field,
+    macros::{DynamicColumn, Fetch},
+    relations::Relation,
+    sqlo::Sqlo,
+    types::is_type_option,
+};
+use darling::util::IdentString;
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Expr};
+
+use super::{list_bindings, WhichMacro};
+
+pub fn expand_select(
+    fetch: Fetch,
+    ident: &IdentString,
+    query: String,
+    arguments: &[&Expr],
+    wich_macro: WhichMacro,
+    lists: &[(syn::Ident, Expr)],
+) -> TokenStream {
+    if !lists.is_empty() {
+        return expand_select_lists(fetch, ident, query, arguments, wich_macro, lists);
+    }
+    match fetch {
+        Fetch::Stream => {
+            quote! {
+                |pool|{
+                    sqlx::query_as!(#ident,#query, #(#arguments),*).#fetch(pool)
+                }
+            }
+        }
+        _ => {
+            if let WhichMacro::Query = wich_macro {
+                quote::quote! {
+                    |pool|
+                        {
+                            sqlx::query!(#query, #(#arguments),*).#fetch(pool)
+                        }
+                }
+            } else {
+                quote::quote! {
+                    |pool|
+                         {
+                        sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool)
+                        }
+
+                }
+            }
+        }
+    }
+}
+
+// Json lists are owned by the future, other arguments are borrowed before to not move them.
+fn expand_select_lists(
+    fetch: Fetch,
+    ident: &IdentString,
+    query: String,
+    arguments: &[&Expr],
+    wich_macro: WhichMacro,
+    lists: &[(syn::Ident, Expr)],
+) -> TokenStream {
+    let mut borrows = vec![];
+    let arguments = arguments
+        .iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            if lists
+                .iter()
+                .any(|(binding, _)| *arg == &syn::parse_quote!(#binding))
+            {
+                quote! {#arg}
+            } else {
+                let borrow = format_ident!("__sqlo_arg_{}", i);
+                borrows.push(quote! {let #borrow = &(#arg);});
+                quote! {*#borrow}
+            }
+        })
+        .collect::<Vec<_>>();
+    let bindings = list_bindings(lists);
+    let call = if let WhichMacro::Query = wich_macro {
+        quote! {sqlx::query!(#query, #(#arguments),*)}
+    } else {
+        quote! {sqlx::query_as!(#ident, #query, #(#arguments),*)}
+    };
+    quote! {
+        |pool| {
+            #(#borrows)*
+            #bindings
+            async move {
+                #call.#fetch(pool).await
+            }
+        }
+    }
+}
+
+// One checked query per column enum variant, futures are boxed so arms share a type.
+// `query!` isn't supported: each call has its own anonymous record type.
+// The value is bound to the column enum first: any other type is reported on it.
+pub fn expand_select_dynamic(
+    fetch: Fetch,
+    ident: &IdentString,
+    sqlo_ident: &IdentString,
+    dynamic_column: &DynamicColumn,
+    query: &str,
+    arguments: &[&Expr],
+) -> TokenStream {
+    let enum_ident = format_ident!("{}Column", sqlo_ident.as_ident());
+    let expr = &dynamic_column.expr;
+    let column = quote_spanned! {expr.span()=> let sqlo_column: #enum_ident = #expr;};
+    let output = match fetch {
+        Fetch::All => quote! {Vec<#ident>},
+        Fetch::One => quote! {#ident},
+        Fetch::Optional => quote! {Option<#ident>},
+        Fetch::None => {
+            let db_path = db_sqlx_path();
+            quote! {<#db_path as sqlx::Database>::QueryResult}
+        }
+        Fetch::Stream => quote! {},
+    };
+    let arms = dynamic_column
+        .queries(query)
+        .into_iter()
+        .map(|(variant, query)| {
+            let call = quote! {sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool)};
+            if let Fetch::Stream = fetch {
+                // already a boxed stream
+                quote! {#enum_ident::#variant => #call}
+            } else {
+                quote! {
+                    #enum_ident::#variant => Box::pin(#call)
+                        as ::std::pin::Pin<Box<dyn ::std::future::Future<Output = sqlx::Result<#output>> + Send + '_>>
+                }
+            }
+        });
+    quote! {
+        |pool| {
+            #column
+            match sqlo_column {
+                #(#arms),*
+            }
+        }
+    }
+}
+
+// Related rows are loaded by a second query: `WHERE fk IN (...)` with every parent's pk,
+// split in many queries to stay under the bind parameters limit of the database.
+pub fn expand_select_include(
+    fetch: Fetch,
+    ident: &IdentString,
+    query: String,
+    arguments: &[&Expr],
+    parent: &Sqlo,
+    child: &Sqlo,
+    relation: &Relation,
+) -> TokenStream {
+    let db_path = db_sqlx_path();
+    let columns = child.fields.iter().map(|f| f.column.as_str()).join(",");
+    let fk = &relation.field;
+    let fk_column = &child
+        .field(fk.as_ident())
+        .expect("Sqlo Internal Error: relation field must exist")
+        .column;
+    let include_query = format!(
+        "SELECT {columns} FROM {} WHERE {fk_column} IN (",
+        child.tablename
+    );
+    let pk_field = parent
+        .pk_field()
+        .expect("Sqlo Internal Error: fk target must have a single primary key");
+    let pk = &pk_field.ident;
+    let pk_ty = &pk_field.ty;
+    let child_ident = &child.ident;
+    let decode = decode_row(child);
+    let push_child = if is_type_option(&relation.ty) {
+        quote! {
+            if let Some(key) = child.#fk.clone() {
+                by_parent.entry(key).or_default().push(child);
+            }
+        }
+    } else {
+        quote! {
+            by_parent.entry(child.#fk.clone()).or_default().push(child);
+        }
+    };
+    let (parents, result) = match fetch {
+        Fetch::All => (quote! {parents.await?}, quote! {grouped}),
+        Fetch::One => (
+            quote! {vec![parents.await?]},
+            quote! {grouped.into_iter().next().expect("one parent was fetched")},
+        ),
+        Fetch::Optional => (
+            quote! {parents.await?.into_iter().collect::<Vec<_>>()},
+            quote! {grouped.into_iter().next()},
+        ),
+        Fetch::Stream | Fetch::None => unreachable!("include fetch mode is checked by generator"),
+    };
+    quote! {
+        |pool| {
+            let parents = sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool);
+            async move {
+                let parents: Vec<#ident> = #parents;
+                let mut by_parent: std::collections::HashMap<#pk_ty, Vec<#child_ident>> =
+                    std::collections::HashMap::new();
+                for chunk in parents.chunks(#MAX_BIND_PARAMS) {
+                    let mut query_builder = sqlx::QueryBuilder::<#db_path>::new(#include_query);
+                    let mut separated = query_builder.separated(", ");
+                    for parent in chunk {
+                        separated.push_bind(parent.#pk.clone());
+                    }
+                    separated.push_unseparated(")");
+                    for row in query_builder.build().fetch_all(pool).await?.iter() {
+                        let child: #child_ident = #decode;
+                        #push_child
+                    }
+                }
+                let grouped = parents
+                    .into_iter()
+                    .map(|parent| {
+                        let related = by_parent.remove(&parent.#pk).unwrap_or_default();
+                        (parent, related)
+                    })
+                    .collect::<Vec<(#ident, Vec<#child_ident>)>>();
+                Ok::<_, sqlx::Error>(#result)
+            }
+        }
+    }
+}
+
+/// Build a sqlo instance from a `sqlx::Row` named `row`, columns are taken by name.
+pub fn decode_row(sqlo: &Sqlo) -> TokenStream {
+    let ident = &sqlo.ident;
+    let fields = sqlo.fields.iter().map(|f| {
+        let Field {
+            ident, column, ty, ..
+        } = f;
+        quote! {#ident: sqlx::Row::try_get::<#ty, _>(row, #column)?}
+    });
+    quote! {#ident { #(#fields),* }}
+}
@@ -10,6 +10,7 @@ pub fn expand_update(
     query: String,
     arguments: &[&Expr],
     move_instance: TokenStream,
+    lists: TokenStream,
 ) -> TokenStream {
     match fetch {
         Fetch::Stream => {
@@ -25,6 +26,7 @@ pub fn expand_update(
                 |pool|{
                     async move {
                     #move_instance
+                    #lists
                     sqlx::query!(#query, #(#arguments),*).#fetch(pool).await
                     }
                 }
@@ -35,6 +37,7 @@ pub fn expand_update(
                 |pool|{
                     async move {
                     #move_instance
+                    #lists
                     sqlx::query_as!(#ident, #query, #(#arguments),*).#fetch(pool).await
                     }
                 }
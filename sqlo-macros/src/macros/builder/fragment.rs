@@ -1,15 +1,16 @@
-use std::{collections::HashSet, ops::Add};
+use std::ops::Add;
 
+use indexmap::IndexSet;
 use syn::Expr;
 
 use crate::error::SqloError;
 
 use super::{Arguments, ColumnToSql, Generator};
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Fragment {
     pub query: String,
     pub params: Arguments,
-    pub joins: HashSet<String>,
+    pub joins: IndexSet<String>, // IndexSet because order matters: a join may use a previous one
 }
 
 // Take  a string query
@@ -18,7 +19,7 @@ impl From<String> for Fragment {
         Fragment {
             query: s,
             params: Arguments::default(),
-            joins: HashSet::default(),
+            joins: IndexSet::default(),
         }
     }
 }
@@ -29,7 +30,7 @@ impl From<&str> for Fragment {
         Fragment {
             query: s.to_string(),
             params: Arguments::default(),
-            joins: HashSet::default(),
+            joins: IndexSet::default(),
         }
     }
 }
@@ -37,7 +38,7 @@ impl From<&str> for Fragment {
 // Take to tupple string (query, join)
 impl From<(String, String)> for Fragment {
     fn from(s: (String, String)) -> Self {
-        let mut h = HashSet::default();
+        let mut h = IndexSet::default();
         h.insert(s.1);
         Fragment {
             query: s.0,
@@ -54,7 +55,7 @@ impl Fragment {
         Fragment {
             query: format!("${}", index),
             params: expr.into(),
-            joins: HashSet::default(),
+            joins: IndexSet::default(),
         }
     }
 }
@@ -72,7 +73,7 @@ impl Add<Fragment> for Fragment {
         Fragment {
             query: format!["{}{}", base_query, rhs.query],
             params: self.params + rhs.params,
-            joins: HashSet::from_iter(self.joins.into_iter().chain(rhs.joins)),
+            joins: IndexSet::from_iter(self.joins.into_iter().chain(rhs.joins)),
         }
     }
 }
@@ -82,7 +83,7 @@ impl Fragment {
         Fragment {
             query: format!["{} {}", self.query, rhs.query],
             params: self.params + rhs.params,
-            joins: HashSet::from_iter(self.joins.into_iter().chain(rhs.joins)),
+            joins: IndexSet::from_iter(self.joins.into_iter().chain(rhs.joins)),
         }
     }
 
@@ -119,7 +120,7 @@ impl<'a> TryFrom<Generator<'a>> for Fragment {
         Ok(Fragment {
             query: result.raw_query()?,
             params: result.arguments,
-            joins: HashSet::default(),
+            joins: IndexSet::default(),
         })
     }
 }
@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+
+use darling::util::IdentString;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    error::SqloError,
+    relations::{ManyToMany, Relation},
+    sqlo::Sqlo,
+    sqlos::Sqlos,
+};
+
+use super::expand_delete;
+use super::expand_insert;
+use super::expand_insert_instances;
+use super::expand_select;
+use super::expand_select_dynamic;
+use super::expand_select_include;
+use super::expand_update;
+use super::list_bindings;
+use super::mode::Mode;
+use super::query_builder::QueryBuilder;
+use super::Arguments;
+use super::Fetch;
+use super::PkValue;
+use super::QueryParser;
+use super::Recursive;
+use super::WhichMacro;
+use super::TREE_DEPTH;
+use super::{Context, TableAliases};
+use crate::macros::DynamicColumn;
+
+pub struct Generator<'a> {
+    pub main_sqlo: &'a Sqlo,
+    pub sqlos: &'a Sqlos,
+    pub aliases: HashMap<IdentString, String>,
+    pub context: Vec<Context>,
+    pub mode: Mode,
+    pub related: Option<&'a Relation>,
+    pub many_to_many: Option<&'a ManyToMany>,
+    pub include: Option<&'a Relation>,
+    pub recursive: Option<(&'a Relation, bool)>, // (self join, toward ancestors)
+    pub custom_struct: Option<IdentString>,
+    pub tables: TableAliases<'a>,
+    pub fetch: Fetch,
+    pub dynamic_column: Option<DynamicColumn>,
+    pub runtime_lists: Vec<(syn::Ident, syn::Expr)>, // (binding, list) of json lists
+    pk_value: PkValue,
+    pub query_parts: QueryBuilder,
+    pub arguments: Arguments,
+}
+
+impl<'a> Generator<'a> {
+    fn new(main_sqlo: &'a Sqlo, sqlos: &'a Sqlos, mode: Mode, tables: TableAliases<'a>) -> Self {
+        Generator {
+            sqlos,
+            main_sqlo,
+            mode,
+            tables,
+            aliases: HashMap::default(),
+            related: Option::default(),
+            many_to_many: Option::default(),
+            include: Option::default(),
+            recursive: Option::default(),
+            query_parts: QueryBuilder::default(),
+            custom_struct: None,
+            context: Vec::default(),
+            fetch: Fetch::default(),
+            dynamic_column: None,
+            runtime_lists: Vec::default(),
+            pk_value: PkValue::None,
+            arguments: Arguments::default(),
+        }
+    }
+
+    pub fn get_main_sqlo<T: QueryParser>(
+        parsed: &T,
+        sqlos: &'a Sqlos,
+    ) -> Result<&'a Sqlo, SqloError> {
+        if parsed.recursive().is_some() {
+            // a self join stays on the same sqlo
+            sqlos.get(parsed.entity())
+        } else if let Some(related) = parsed.related() {
+            sqlos
+                .get_by_relation(parsed.entity(), related)
+                .or_else(
+                    |err| match sqlos.get_many_to_many(parsed.entity(), related) {
+                        Ok(rel) => sqlos.get(&rel.to),
+                        Err(_) => Err(err),
+                    },
+                )
+        } else {
+            sqlos.get(parsed.entity())
+        }
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn process_from(&mut self) {
+        if !self.tables.contains(&self.main_sqlo.ident) {
+            self.tables.insert_sqlo(&self.main_sqlo.ident)
+        }
+    }
+
+    fn set_relation_if_related<T: QueryParser>(&mut self, parsed: &T) -> Result<(), SqloError> {
+        if let (Some(recursive), Some(related)) = (parsed.recursive(), parsed.related()) {
+            return self.set_recursive(parsed.entity(), related, recursive);
+        }
+        if let Some(related) = parsed.related() {
+            match self.sqlos.get_relation(parsed.entity(), related) {
+                Ok(rel) => self.related = Some(rel),
+                Err(err) => {
+                    let rel = self
+                        .sqlos
+                        .get_many_to_many(parsed.entity(), related)
+                        .map_err(|_| err)?;
+                    if !matches!(self.mode, Mode::Select) {
+                        return Err(SqloError::new_spanned(
+                            related,
+                            "many to many relation is only supported by select!",
+                        ));
+                    }
+                    self.many_to_many = Some(rel);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // children are reached with the related name, parents with the parent name
+    fn set_recursive(
+        &mut self,
+        entity: &IdentString,
+        related: &IdentString,
+        recursive: &Recursive,
+    ) -> Result<(), SqloError> {
+        let (relation, ancestors) = match self.sqlos.get_relation(entity, related) {
+            Ok(rel) => (rel, false),
+            Err(err) => (
+                self.sqlos
+                    .get_parent_relation(entity, related)
+                    .map_err(|_| err)?,
+                true,
+            ),
+        };
+        if !relation.is_self_join() {
+            return Err(SqloError::new_spanned(
+                recursive,
+                "`recursive` is only supported with self join",
+            ));
+        }
+        if !matches!(self.pk_value, PkValue::Bracketed(_)) {
+            return Err(SqloError::new_spanned(
+                recursive,
+                "`recursive` needs a primary key value: `Entity[pk].related recursive`",
+            ));
+        }
+        let sqlo = self.sqlos.get(entity)?;
+        if sqlo
+            .fields
+            .iter()
+            .any(|f| f.ident == TREE_DEPTH || f.column == TREE_DEPTH)
+        {
+            return Err(SqloError::new_spanned(
+                recursive,
+                format!(
+                    "`{TREE_DEPTH}` is reserved by `recursive`, rename this field or its column"
+                ),
+            ));
+        }
+        self.recursive = Some((relation, ancestors));
+        Ok(())
+    }
+
+    fn set_include<T: QueryParser>(&mut self, parsed: &T) -> Result<(), SqloError> {
+        if let Some(include) = parsed.include() {
+            if self.context.contains(&Context::SubQuery)
+                || !parsed.columns().is_empty()
+                || parsed.custom_struct().is_some()
+            {
+                return Err(SqloError::new_spanned(
+                    include,
+                    "`include` is only supported when selecting whole sqlo rows",
+                ));
+            }
+            if let Fetch::Stream | Fetch::None = parsed.fetch() {
+                return Err(SqloError::new_spanned(
+                    include,
+                    "`include` needs `*`, `.` or `?` fetch mode",
+                ));
+            }
+            let relation = self.sqlos.get_relation(&self.main_sqlo.ident, include)?;
+            if relation.is_self_join() {
+                return Err(SqloError::new_spanned(
+                    include,
+                    "`include` is not supported with self join",
+                ));
+            }
+            self.include = Some(relation);
+        }
+        Ok(())
+    }
+
+    fn process_fetch<T: QueryParser>(&mut self, parsed: &T) {
+        self.fetch = parsed.fetch()
+    }
+
+    fn process_pk_value<T: QueryParser>(&mut self, parsed: &T) {
+        self.pk_value = parsed.pk_value();
+    }
+
+    fn parse<T: QueryParser>(&mut self, parsed: T) -> Result<(), SqloError> {
+        // preleminary
+        self.process_from();
+        self.process_pk_value(&parsed);
+        self.set_relation_if_related(&parsed)?;
+        self.set_include(&parsed)?;
+        self.process_fetch(&parsed);
+        self.custom_struct = parsed.custom_struct();
+        // query_parts
+        let mut qp = QueryBuilder::default();
+        qp.parse(&parsed, self)?;
+        self.query_parts = qp;
+        Ok(())
+    }
+}
+
+// publique interface
+impl<'a> Generator<'_> {
+    pub fn from_sqlo_query_parse<T>(
+        mode: Mode,
+        parsed: T,
+        sqlos: &'a Sqlos,
+        subquery: bool,
+        table_aliases: TableAliases<'a>,
+    ) -> Result<Generator<'a>, SqloError>
+    where
+        T: QueryParser,
+    {
+        let main_sqlo = Generator::get_main_sqlo(&parsed, sqlos)?;
+        let mut generator = Generator::new(main_sqlo, sqlos, mode, table_aliases);
+        if subquery {
+            generator.context.push(Context::SubQuery);
+        }
+        generator.parse(parsed)?;
+        Ok(generator)
+    }
+
+    pub fn expand(&self, #[cfg(debug_assertions)] debug: bool) -> Result<TokenStream, SqloError> {
+        if let (Mode::Insert, PkValue::Parenthezide(instances)) = (&self.mode, &self.pk_value) {
+            let on_conflict = self.query_parts.on_conflict();
+            return Ok(expand_insert_instances(
+                self.fetch,
+                instances,
+                self.main_sqlo,
+                on_conflict,
+                self.arguments.as_sequence(on_conflict).as_slice(),
+            ));
+        }
+        let initial_query = self.query_parts.query(self)?;
+        let arguments = self.arguments.as_result(&initial_query);
+        let query = self.format_query(&initial_query);
+        let fetch = self.fetch;
+        let ident = if let Some(ident) = &self.custom_struct {
+            ident
+        } else {
+            &self.main_sqlo.ident
+        };
+
+        #[cfg(debug_assertions)]
+        self.debug(&query, debug);
+
+        if let Some((_, list)) = self.runtime_lists.first() {
+            if self.include.is_some()
+                || self.dynamic_column.is_some()
+                || matches!(fetch, Fetch::Stream)
+            {
+                return Err(SqloError::new_spanned(
+                    list,
+                    "`in` with a runtime list is not supported with `+`, include or column enum with this database",
+                ));
+            }
+        }
+
+        if let Some(relation) = self.include {
+            return Ok(expand_select_include(
+                fetch,
+                ident,
+                query,
+                arguments.as_slice(),
+                self.main_sqlo,
+                self.sqlos.get(&relation.from)?,
+                relation,
+            ));
+        }
+
+        if let Some(dynamic_column) = &self.dynamic_column {
+            if let WhichMacro::Query = WhichMacro::for_select(self) {
+                return Err(SqloError::new_spanned(
+                    &dynamic_column.expr,
+                    "column enum in order_by needs whole sqlo rows or a custom struct",
+                ));
+            }
+            return Ok(expand_select_dynamic(
+                fetch,
+                ident,
+                &self.main_sqlo.ident,
+                dynamic_column,
+                &query,
+                arguments.as_slice(),
+            ));
+        }
+
+        match self.mode {
+            Mode::Select => Ok(expand_select(
+                fetch,
+                ident,
+                query,
+                arguments.as_slice(),
+                WhichMacro::for_select(self),
+                &self.runtime_lists,
+            )),
+            Mode::Update => Ok(expand_update(
+                fetch,
+                ident,
+                query,
+                arguments.as_slice(),
+                self.move_instance(),
+                list_bindings(&self.runtime_lists),
+            )),
+            Mode::Insert => Ok(expand_insert(
+                fetch,
+                ident,
+                query,
+                arguments.as_slice(),
+                self.main_sqlo,
+            )),
+            Mode::Delete => Ok(expand_delete(
+                fetch,
+                ident,
+                query,
+                arguments.as_slice(),
+                self.move_instance(),
+                list_bindings(&self.runtime_lists),
+            )),
+        }
+    }
+
+    // instance syntax `Entity(instance)` takes ownership of instance
+    fn move_instance(&self) -> TokenStream {
+        if let PkValue::Parenthezide(instance) = &self.pk_value {
+            quote! {let #instance = #instance;}
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn debug(&self, query: &str, debug: bool) {
+        if std::env::var("SQLO_DEBUG_QUERY").is_ok() {
+            println!("query: {}", &query);
+        } else if std::env::var("SQLO_DEBUG_QUERY_ALL").is_ok() {
+            let dd = format!("query: {} \n args: {:?}", &query, &self.arguments);
+            println!("{}", dd);
+        } else if debug {
+            println!("query: {} \nargs: {:?}", query, self.arguments);
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    fn format_query(&self, query: &str) -> String {
+        query.to_string()
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    fn format_query(&self, query: &str) -> String {
+        let res = query.to_string();
+        regex_macro::regex!(r"\$\d+")
+            .replace_all(&res, "?")
+            .to_string()
+    }
+
+    pub fn raw_query(&self) -> Result<String, SqloError> {
+        self.query_parts.query(self)
+    }
+}
@@ -0,0 +1,35 @@
+use syn::{parse::ParseStream, Token};
+
+pub mod kw {
+
+    syn::custom_keyword!(order_by);
+    syn::custom_keyword!(limit);
+    syn::custom_keyword!(page);
+    syn::custom_keyword!(group_by);
+    syn::custom_keyword!(having);
+    syn::custom_keyword!(on_conflict);
+    syn::custom_keyword!(update);
+    syn::custom_keyword!(nothing);
+    syn::custom_keyword!(include);
+    syn::custom_keyword!(over);
+    syn::custom_keyword!(partition_by);
+    syn::custom_keyword!(with);
+    syn::custom_keyword!(recursive);
+    syn::custom_keyword!(depth);
+    syn::custom_keyword!(union);
+    syn::custom_keyword!(all);
+    syn::custom_keyword!(intersect);
+    syn::custom_keyword!(except);
+}
+pub fn next_is_not_a_keyword(input: &ParseStream) -> bool {
+    !input.peek(Token![where])
+        && !input.peek(kw::order_by)
+        && !input.peek(kw::limit)
+        && !input.peek(kw::page)
+        && !input.peek(kw::group_by)
+        && !input.peek(kw::having)
+        && !input.peek(kw::on_conflict)
+        && !input.peek(kw::union)
+        && !input.peek(kw::intersect)
+        && !input.peek(kw::except)
+}
@@ -1,5 +1,6 @@
 mod arguments;
 mod column_to_sql;
+mod compound;
 mod context;
 mod expander;
 mod fetch;
@@ -11,10 +12,12 @@ mod operator;
 mod parsers;
 mod query_builder;
 mod query_parser;
+mod recursive;
 mod table_aliases;
 
 pub use arguments::Arguments;
 pub use column_to_sql::ColumnToSql;
+pub use compound::{Compound, SetOperator};
 pub use context::Context;
 pub use expander::*;
 pub use fetch::Fetch;
@@ -25,4 +28,5 @@ pub use mode::Mode;
 pub use operator::Operator;
 pub use parsers::*;
 pub use query_parser::{PkValue, QueryParser};
+pub use recursive::{Recursive, TREE_DEPTH, TREE_TABLE};
 pub use table_aliases::TableAliases;
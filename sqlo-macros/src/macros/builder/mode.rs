@@ -0,0 +1,140 @@
+use crate::macros::{DeleteParser, InsertParser, SelectParser, UpdateParser};
+
+use super::{QueryParser, TableAliases};
+#[derive(Debug, Clone, Copy)]
+pub enum Mode {
+    Select,
+    Update,
+    Insert,
+    Delete,
+}
+
+impl Mode {
+    /// run the macros depending mode
+    pub fn process(
+        self,
+        input: proc_macro::TokenStream,
+    ) -> Result<proc_macro2::TokenStream, crate::SqloError> {
+        match self {
+            Self::Select => self.expand(syn::parse::<SelectParser>(input)?),
+            Self::Update => self.expand(syn::parse::<UpdateParser>(input)?),
+            Self::Insert => self.expand(syn::parse::<InsertParser>(input)?),
+            Self::Delete => self.expand(syn::parse::<DeleteParser>(input)?),
+        }
+    }
+}
+impl Mode {
+    pub fn expand<T>(self, parsed: T) -> Result<proc_macro2::TokenStream, crate::SqloError>
+    where
+        T: QueryParser,
+    {
+        let sqlos = crate::VirtualFile::new().load()?;
+        self.expand_with_sqlos(parsed, sqlos)
+    }
+
+    /// run the macro against the given sqlos
+    pub fn expand_with_sqlos<T>(
+        self,
+        parsed: T,
+        mut sqlos: crate::sqlos::Sqlos,
+    ) -> Result<proc_macro2::TokenStream, crate::SqloError>
+    where
+        T: QueryParser,
+    {
+        #[cfg(debug_assertions)]
+        let debug = parsed.debug();
+
+        crate::macros::extend_sqlos(&parsed, &mut sqlos)?;
+        let tables: TableAliases = TableAliases::new(&sqlos);
+        let generator =
+            crate::macros::Generator::from_sqlo_query_parse(self, parsed, &sqlos, false, tables)?;
+
+        generator.expand(
+            #[cfg(debug_assertions)]
+            debug,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_mode {
+    use super::*;
+    use crate::sqlos::Sqlos;
+    use quote::quote;
+
+    fn sqlos() -> Sqlos {
+        Sqlos::from_derive_inputs(&[
+            syn::parse_quote! {
+                #[sqlo(tablename = "maison")]
+                struct Maison {
+                    id: i64,
+                    adresse: String,
+                    taille: i64,
+                }
+            },
+            syn::parse_quote! {
+                #[sqlo(tablename = "piece")]
+                struct Piece {
+                    #[sqlo(primary_key)]
+                    nb: i64,
+                    lg: i64,
+                    #[sqlo(fk = "Maison", related = "lespieces")]
+                    maison_id: i64,
+                }
+            },
+            syn::parse_quote! {
+                #[sqlo(tablename = "noeud")]
+                struct Noeud {
+                    id: i64,
+                    sqlo_depth: i64,
+                    #[sqlo(fk = "Noeud", related = "enfants")]
+                    parent_id: Option<i64>,
+                }
+            },
+        ])
+    }
+
+    fn expand_err<T: QueryParser + syn::parse::Parse>(
+        mode: Mode,
+        tokens: proc_macro2::TokenStream,
+    ) -> String {
+        let parsed = syn::parse2::<T>(tokens).unwrap();
+        mode.expand_with_sqlos(parsed, sqlos())
+            .unwrap_err()
+            .msg()
+            .to_string()
+    }
+
+    #[test]
+    fn delete_with_relation_field_is_an_error() {
+        let msg = expand_err::<DeleteParser>(Mode::Delete, quote! {Maison where lespieces.lg > 1});
+        assert!(msg.contains("only supported by select!"), "{msg}");
+        let msg = expand_err::<DeleteParser>(Mode::Delete, quote! {Piece where maison.taille > 1});
+        assert!(msg.contains("only supported by select!"), "{msg}");
+    }
+
+    #[test]
+    fn nested_cte_is_only_known_in_its_query() {
+        let msg = expand_err::<SelectParser>(
+            Mode::Select,
+            quote! {*Maison where id in {with big = {Maison id} big id} && id in {big id}},
+        );
+        assert!(msg.contains("`big`"), "{msg}");
+    }
+
+    #[test]
+    fn recursive_depth_column_is_reserved() {
+        let msg =
+            expand_err::<SelectParser>(Mode::Select, quote! {*Noeud[1].enfants recursive depth 3});
+        assert!(msg.contains("`sqlo_depth` is reserved"), "{msg}");
+    }
+
+    #[test]
+    fn update_with_relation_field_is_an_error() {
+        let msg = expand_err::<UpdateParser>(
+            Mode::Update,
+            quote! {Maison[1] taille=1 where lespieces.lg > 1},
+        );
+        assert!(msg.contains("only supported by select!"), "{msg}");
+    }
+}
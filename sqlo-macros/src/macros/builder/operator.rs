@@ -16,25 +16,36 @@ pub enum Operator {
     And,
     Or,
     Like,
+    NotLike,
+    ILike,
+    NotILike,
     In,
+    NotIn,
+    NullSafeEq,
+    NullSafeNeq,
+    Regex,
+    NotRegex,
 }
+
+impl Operator {
+    pub fn is_logical(&self) -> bool {
+        matches!(self, Self::And | Self::Or)
+    }
+}
+
+// an operator may be a sequence of tokens, like `! in`
 macro_rules! impl_parse_for_operator {
-    ($start:ident, $start_sign:tt, $($iden:ident,$sign:tt),+) => {
+    ($($iden:ident ($($sign:tt)+)),+) => {
     impl syn::parse::Parse for Operator {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-            if input.peek(syn::Token![$start_sign]) {
-                input.parse::<syn::Token![$start_sign]>()?;
-                return Ok(Operator::$start)
-            }
             $(
-            else if input.peek(syn::Token![$sign]) {
-                input.parse::<syn::Token![$sign]>()?;
+            let fork = input.fork();
+            if true $(&& fork.parse::<syn::Token![$sign]>().is_ok())+ {
+                $(input.parse::<syn::Token![$sign]>()?;)+
                 return Ok(Operator::$iden)
             }
             )+
-            else {
-                return Err(input.error("Operator not supported"));
-            }
+            Err(input.error("Operator not supported"))
         }
     }
     }
@@ -74,23 +85,36 @@ macro_rules! impl_is_next_supported_operator {
     pub fn next_is_supported_op(input: &syn::parse::ParseStream) -> bool {
         input.peek(syn::Token![$start])
             $(|| input.peek(syn::Token![$other]))+
+            // negated: `!in`, `!#`, `!~`, `!<=>`, but not a `column!` in select
+            || (input.peek(syn::Token![!])
+                && (input.peek2(syn::Token![in])
+                    || input.peek2(syn::Token![#])
+                    || input.peek2(syn::Token![~])
+                    || input.peek2(syn::Token![<])))
     }
     }
     };
 }
 
 impl_parse_for_operator!(
-    Add,+, Sub,-, Mul,*, Div,/, Mod,%,
-    Eq,==, Neq, !=, Ge,>=, Le, <=,Lt,<, Gt,>, //order matter composed before single token
-    And,&&, Or,||, //Not, !
-    Like,#, In, in
+    Add(+), Sub(-), Mul(*), Div(/), Mod(%),
+    //order matter composed before single token
+    NullSafeEq(<= >), NullSafeNeq(! <= >),
+    Eq(==), Neq(!=), Ge(>=), Le(<=), Lt(<), Gt(>),
+    And(&&), Or(||), //Not, !
+    NotILike(! # *), NotLike(! #), ILike(# *), Like(#),
+    NotIn(! in), In(in),
+    NotRegex(! ~), Regex(~)
 );
 
 impl_columnto_sql_for_operator!(
     Add "+", Sub "-", Mul "*", Div "/", Mod "%",
     Ge ">=", Le "<=", Lt "<", Gt ">",  Eq "=", Neq "<>",
     And "AND", Or "OR",
-    Like "LIKE", In "IN"
+    Like "LIKE", NotLike "NOT LIKE", ILike "ILIKE", NotILike "NOT ILIKE",
+    In "IN", NotIn "NOT IN",
+    NullSafeEq "IS NOT DISTINCT FROM", NullSafeNeq "IS DISTINCT FROM",
+    Regex "REGEXP", NotRegex "NOT REGEXP"
 
 );
 
@@ -98,7 +122,10 @@ impl_to_tokens_for_operator!(
     Add "+", Sub "-", Mul "*", Div "/", Mod "%",
     Ge ">=", Le "<=", Lt "<", Gt ">", Eq "==", Neq "!=",
     And "&&", Or "||",
-    Like "#", In "in"
+    Like "#", NotLike "!#", ILike "#*", NotILike "!#*",
+    In "in", NotIn "!in",
+    NullSafeEq "<=>", NullSafeNeq "!<=>",
+    Regex "~", NotRegex "!~"
 );
 // Self::Not => Ok("NOT".into()),
 
@@ -106,5 +133,5 @@ impl_is_next_supported_operator!(
     +, -, *, /, %,
     <=, >=, <, >, ==, !=,
     &&, ||,
-    #, in
+    #, in, ~
 );
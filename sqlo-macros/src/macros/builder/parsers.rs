@@ -7,7 +7,7 @@ use syn::{
 
 use crate::macros::{Column, PkValue};
 
-use super::next_is_not_a_keyword;
+use super::{kw, next_is_not_a_keyword};
 
 pub fn parse_identstring(input: ParseStream) -> syn::Result<IdentString> {
     input.parse::<syn::Ident>().map(|x| x.into())
@@ -61,6 +61,16 @@ pub fn parse_optional_field_member(input: ParseStream) -> syn::Result<Option<Ide
     }
 }
 
+pub fn parse_optional_include(input: ParseStream) -> syn::Result<Option<IdentString>> {
+    // `include` alone is still a column name
+    if input.peek(kw::include) && input.peek2(syn::Ident) {
+        input.parse::<kw::include>()?;
+        input.call(parse_identstring).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn parse_columns(input: ParseStream) -> syn::Result<Punctuated<Column, Token![,]>> {
     Punctuated::parse_separated_nonempty(input)
 }
@@ -108,7 +118,9 @@ impl_parse_optional_clauses! {
 group_by GroupBy;
 having Having;
 order_by OrderBy;
-limit,page Limit
+limit,page Limit;
+on_conflict OnConflict;
+with With
 }
 
 impl_parse_optional_clauses! {# where Where}
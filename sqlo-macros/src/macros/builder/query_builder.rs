@@ -0,0 +1,502 @@
+use std::fmt::Write;
+
+use darling::util::IdentString;
+use indexmap::IndexSet;
+use itertools::Itertools;
+use syn::Expr;
+
+use super::{Context, Fragment, Generator, Mode, PkValue, QueryParser, TREE_DEPTH, TREE_TABLE};
+
+use crate::{
+    macros::{Assigns, Clause, ColExpr, ColExprField, ColExprOp, ColumnToSql},
+    relations::Join,
+    utils::INSERT_FN_FLAG,
+    SqloError,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct QueryBuilder {
+    ctes: Vec<String>,
+    recursive: bool,
+    compound: String,
+    subjects: String,
+    joins: IndexSet<String>,
+    wwhere: String,
+    group_by: String,
+    order_by: String,
+    having: String,
+    limit: String,
+    on_conflict: String,
+    tablename: String,
+    pub customs: bool,
+}
+
+impl QueryBuilder {
+    pub fn extend(&mut self, qr: Fragment, ctx: &mut Generator) {
+        ctx.arguments.extend(qr.params);
+        self.joins.extend(qr.joins);
+    }
+
+    fn link_related_entity<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        let idents = self.get_idents_from_related(ctx)?;
+        let values: Vec<Expr> = match parsed.pk_value() {
+            PkValue::Bracketed(pk) => {
+                if idents.len() == 1 {
+                    vec![pk]
+                } else {
+                    match pk {
+                        Expr::Tuple(tuple) if tuple.elems.len() == idents.len() => {
+                            tuple.elems.into_iter().collect()
+                        }
+                        _ => {
+                            return Err(SqloError::new_spanned(
+                                pk,
+                                format!(
+                                    "Composite primary key expects a tuple of {} values",
+                                    idents.len()
+                                ),
+                            ))
+                        }
+                    }
+                }
+            }
+            PkValue::Parenthezide(pk) => {
+                if let Expr::Path(p) = pk {
+                    ctx.sqlos
+                        .get(parsed.entity())?
+                        .pk_fields
+                        .iter()
+                        .map(|f| {
+                            let pk_field = &f.ident;
+                            syn::parse_quote! {#p.#pk_field}
+                        })
+                        .collect()
+                } else {
+                    return Err(SqloError::new_spanned(
+                        pk,
+                        "Unsupported format. Must be an instance of derived Sqlo struct",
+                    ));
+                }
+            }
+            PkValue::None => return Ok(()), // nothing to be seen here
+        };
+        for (ident, value) in idents.into_iter().zip(values) {
+            let op = ColExprOp {
+                lhs: Box::new(ident),
+                op: super::Operator::Eq,
+                rhs: Box::new(ColExpr::Value(value)),
+            };
+            self.append_related_fragment_to_query(op, ctx)?;
+        }
+        Ok(())
+    }
+
+    fn get_idents_from_related(&mut self, ctx: &mut Generator) -> Result<Vec<ColExpr>, SqloError> {
+        if let Some(relation) = ctx.many_to_many {
+            // the join table is always needed, even without pk value
+            let (join, field) = relation.to_through_join(ctx)?;
+            self.joins.insert(join);
+            let through = relation.through.as_ident().clone();
+            return Ok(vec![ColExprField::new(
+                through,
+                field.as_ident().clone(),
+                Join::Inner,
+            )
+            .into()]);
+        }
+        let idents = if let Some(relation) = ctx.related {
+            vec![relation.field.clone()]
+        } else {
+            ctx.main_sqlo
+                .pk_fields
+                .iter()
+                .map(|f| f.ident.clone())
+                .collect()
+        };
+        Ok(idents.into_iter().map(ColExpr::from).collect())
+    }
+
+    fn append_related_fragment_to_query(
+        &mut self,
+        op: ColExprOp,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        let frag = op.column_to_sql(ctx)?;
+        if self.wwhere.is_empty() {
+            self.wwhere = format!(" WHERE {}", &frag.query)
+        } else {
+            write!(self.wwhere, " AND {}", &frag.query)?;
+        }
+        self.extend(frag, ctx);
+        Ok(())
+    }
+
+    pub fn set_columns<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        if parsed.columns().is_empty() {
+            let subquery = ctx.context.contains(&Context::SubQuery);
+            if ctx.main_sqlo.pseudo && ctx.custom_struct.is_none() && !subquery {
+                return Err(SqloError::new_spanned(
+                    parsed.entity(),
+                    format!(
+                        "`{}` isn't a Sqlo struct: select columns or use a custom struct",
+                        parsed.entity()
+                    ),
+                ));
+            }
+            let mut res = vec![];
+            for f in ctx.main_sqlo.fields.iter() {
+                // we write full query if name or type isn't the same between rust struct and database
+                // but not in subqueries where column names must stay plain
+                if !subquery && (f.type_override || f.ident != f.column || f.ident == "id") {
+                    let a = format!(
+                        r#"{} as "{}:_""#,
+                        &ctx.tables
+                            .alias_dot_column(&ctx.main_sqlo.ident, &f.ident)?,
+                        &f.ident
+                    )
+                    .replace('\\', "");
+                    res.push(a);
+                } else {
+                    res.push(
+                        ctx.tables
+                            .alias_dot_column(&ctx.main_sqlo.ident, &f.ident)?,
+                    )
+                }
+            }
+            self.subjects = res.join(", ");
+        } else {
+            self.customs = true;
+            let columns = parsed.columns().iter().fold(
+                Ok(Fragment::default()),
+                |acc: Result<Fragment, SqloError>, nex| Ok(acc? + nex.column_to_sql(ctx)?),
+            )?;
+            self.subjects = columns.query.clone();
+            self.extend(columns, ctx);
+        }
+        Ok(())
+    }
+
+    pub fn set_assigns<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        let qr = parsed.assigns().column_to_sql(ctx)?;
+        self.subjects = qr.query.clone();
+        self.extend(qr, ctx);
+        Ok(())
+    }
+
+    pub fn set_values<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        let rows = parsed.rows();
+        if rows.is_empty() {
+            // instances are inserted at runtime
+            return Ok(());
+        }
+        let mut columns: Option<Vec<IdentString>> = None;
+        let mut values = vec![];
+        for (idx, assigns) in rows.iter().enumerate() {
+            // each row has its own insert_fn call
+            let flag_suffix = if rows.len() > 1 {
+                format!("_{idx}")
+            } else {
+                String::new()
+            };
+            let (row_columns, arguments) = Self::row_values(assigns, &flag_suffix, ctx)?;
+            match &columns {
+                Some(cols) if cols != &row_columns => {
+                    return Err(SqloError::new_spanned(
+                        assigns,
+                        "Every row of a multi rows insert must set the same fields",
+                    ))
+                }
+                Some(_) => {}
+                None => columns = Some(row_columns),
+            }
+            values.push(format!("({})", &arguments.query));
+            self.extend(arguments, ctx);
+        }
+        let columns = columns.unwrap_or_default().into_iter().join(
+            "
+            ,",
+        );
+        self.subjects = format!("({}) VALUES {}", columns, values.join(", "));
+        Ok(())
+    }
+
+    fn row_values(
+        assigns: &Assigns,
+        flag_suffix: &str,
+        ctx: &mut Generator,
+    ) -> Result<(Vec<IdentString>, Fragment), SqloError> {
+        let multi_rows = !flag_suffix.is_empty();
+        let mut arguments = Fragment::default();
+        let mut columns = vec![];
+        for f in &ctx.main_sqlo.fields {
+            if let Some(value) = assigns.value(&f.ident) {
+                match value {
+                    ColExpr::Ident(ident) if ident.as_str() == "None" => {
+                        if multi_rows {
+                            // keep the same columns for every row
+                            arguments = arguments + Fragment::from("NULL");
+                        } else {
+                            continue;
+                        }
+                    }
+                    _ => arguments = arguments + value.column_to_sql(ctx)?,
+                };
+                columns.push(f.ident.clone());
+            } else if ctx.main_sqlo.single_pk_field() == Some(f) && f.insert_fn.is_some() {
+                // use insert_fn if no pk is given
+                let ident = syn::Ident::new(INSERT_FN_FLAG, proc_macro2::Span::call_site());
+                let arg_ident = syn::Ident::new(
+                    &format!("{INSERT_FN_FLAG}{flag_suffix}"),
+                    proc_macro2::Span::call_site(),
+                );
+                let arg: syn::Expr = syn::parse_quote! {#arg_ident};
+                arguments = arguments + arg.column_to_sql(ctx)?;
+                let ident_insert_fn = IdentString::new(ident);
+                columns.push(ident_insert_fn);
+            }
+        }
+        Ok((columns, arguments))
+    }
+
+    fn set_tablename(&mut self, ctx: &Generator) -> Result<(), SqloError> {
+        if ctx.recursive.is_some() {
+            let (alias, _) = ctx.tables.get(&ctx.main_sqlo.ident)?;
+            self.tablename = format!("{TREE_TABLE} {alias}");
+            return Ok(());
+        }
+        self.tablename = match ctx.mode {
+            Mode::Select => ctx.tables.tablename_with_alias(&ctx.main_sqlo.ident)?,
+            _ => ctx.tables.tablename(&ctx.main_sqlo.ident)?,
+        };
+        Ok(())
+    }
+
+    fn get_distinct(&self, ctx: &Generator) -> &str {
+        // operands of a set operation keep their rows, the operator alone decides of duplicates
+        if !self.compound.is_empty() || ctx.context.contains(&Context::Compound) {
+            return "";
+        }
+        // auto add distinct
+        // non need of distinct for plain sqlo struct query if no join.
+        // but necessary for everything else
+        if self.customs || ctx.custom_struct.is_some() || !self.joins.is_empty() {
+            " DISTINCT"
+        } else {
+            ""
+        }
+    }
+
+    pub fn parse<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        self.set_tablename(ctx)?;
+        self.set_derived(parsed, ctx)?;
+        self.set_tree(parsed, ctx)?;
+        match ctx.mode {
+            Mode::Select => self.set_columns(parsed, ctx)?,
+            Mode::Update => self.set_assigns(parsed, ctx)?,
+            Mode::Insert => self.set_values(parsed, ctx)?,
+            Mode::Delete => {} // nothing but clauses
+        };
+        // columns of a combined query, for its order_by
+        let mut compound_columns = None;
+        if let Some(compound) = parsed.compound() {
+            compound_columns = Some(compound.columns(parsed, ctx.sqlos)?);
+            let qr = compound.column_to_sql(ctx)?;
+            self.compound = qr.query.clone();
+            self.extend(qr, ctx);
+        }
+        for clause in parsed.clauses().iter() {
+            match clause {
+                // order matters
+                Clause::Where(x) => {
+                    let qr = x.column_to_sql(ctx)?;
+                    self.wwhere = qr.query.clone();
+                    self.extend(qr, ctx);
+                }
+                Clause::GroupBy(x) => {
+                    let qr = x.column_to_sql(ctx)?;
+                    self.group_by = qr.query.clone();
+                    self.extend(qr, ctx);
+                }
+                Clause::Having(x) => {
+                    let qr = x.column_to_sql(ctx)?;
+                    self.having = qr.query.clone();
+                    self.extend(qr, ctx);
+                }
+                Clause::OrderBy(x) => {
+                    let qr = match &compound_columns {
+                        Some(columns) => x.positions_to_sql(columns)?,
+                        None => x.column_to_sql(ctx)?,
+                    };
+                    self.order_by = qr.query.clone();
+                    self.extend(qr, ctx);
+                }
+                Clause::Limit(x) => {
+                    let qr = x.column_to_sql(ctx)?;
+                    self.limit = qr.query.clone();
+                    self.extend(qr, ctx);
+                }
+                Clause::OnConflict(x) => {
+                    let qr = x.column_to_sql(ctx)?;
+                    self.on_conflict = qr.query.clone();
+                    self.extend(qr, ctx);
+                }
+                Clause::With(x) => {
+                    let qr = x.column_to_sql(ctx)?;
+                    self.ctes.push(qr.query.clone());
+                    self.extend(qr, ctx);
+                }
+            }
+        }
+        if let Mode::Insert = ctx.mode {
+            return Ok(());
+        }
+
+        if ctx.recursive.is_none() {
+            self.link_related_entity(parsed, ctx)?;
+        }
+        Ok(())
+    }
+
+    // subquery in place of the table
+    fn set_derived<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        if let Some(derived) = parsed.derived() {
+            let (alias, _) = ctx.tables.get(&ctx.main_sqlo.ident)?;
+            let alias = *alias;
+            self.tablename = format!("({}) {alias}", derived.query_to_sql(ctx)?);
+        }
+        Ok(())
+    }
+
+    // the tree is queried in place of the table, its depth is usable as a column
+    fn set_tree<T: QueryParser>(
+        &mut self,
+        parsed: &T,
+        ctx: &mut Generator,
+    ) -> Result<(), SqloError> {
+        let (relation, ancestors, recursive, pk) =
+            match (ctx.recursive, parsed.recursive(), parsed.pk_value()) {
+                (Some((relation, ancestors)), Some(recursive), PkValue::Bracketed(pk)) => {
+                    (relation, ancestors, recursive, pk)
+                }
+                _ => return Ok(()),
+            };
+        let qr = recursive.tree_to_sql(relation, ancestors, &pk, ctx)?;
+        self.ctes.push(qr.query.clone());
+        self.recursive = true;
+        self.extend(qr, ctx);
+        let (alias, _) = ctx.tables.get(&ctx.main_sqlo.ident)?;
+        let depth = IdentString::new(syn::Ident::new(TREE_DEPTH, proc_macro2::Span::call_site()));
+        ctx.aliases.insert(depth, format!("{alias}.{TREE_DEPTH}"));
+        Ok(())
+    }
+
+    pub fn on_conflict(&self) -> &str {
+        &self.on_conflict
+    }
+
+    pub fn query(&self, ctx: &Generator) -> Result<String, SqloError> {
+        let query = match ctx.mode {
+            Mode::Select => self.query_select(ctx)?,
+            Mode::Update => self.query_update(ctx)?,
+            Mode::Insert => self.query_insert(ctx)?,
+            Mode::Delete => self.query_delete(ctx)?,
+        };
+        let query: String = query.trim().into();
+        let res = query;
+        Ok(res)
+    }
+    pub fn query_select(&self, ctx: &Generator) -> Result<String, SqloError> {
+        let with = if self.ctes.is_empty() {
+            String::new()
+        } else if self.recursive {
+            format!("WITH RECURSIVE {} ", self.ctes.join(", "))
+        } else {
+            format!("WITH {} ", self.ctes.join(", "))
+        };
+        let distinct = self.get_distinct(ctx);
+        let subjects = &self.subjects;
+        let tablename = &self.tablename;
+        let joins = self.joins.iter().join(" ");
+        let where_query = &self.wwhere;
+        let group_by_query = &self.group_by;
+        let having_query = &self.having;
+        let compound = &self.compound;
+        let order_by_query = &self.order_by;
+        let limit_query = &self.limit;
+
+        Ok(format!("{with}SELECT{distinct} {subjects} FROM {tablename}{joins}{where_query}{group_by_query}{having_query}{compound}{order_by_query}{limit_query}"))
+    }
+
+    fn query_update(&self, ctx: &Generator) -> Result<String, SqloError> {
+        let subjects = &self.subjects;
+        let tablename = &self.tablename;
+        let where_query = &self.wwhere;
+        let returning_columns = ctx.main_sqlo.to_non_null_columns();
+
+        let returning = if ctx.fetch.is_returning() {
+            format!(" RETURNING {}", returning_columns)
+        } else {
+            "".to_string()
+        };
+
+        Ok(format!(
+            "UPDATE {tablename} SET {subjects}{where_query}{returning}"
+        ))
+    }
+
+    fn query_insert(&self, ctx: &Generator) -> Result<String, SqloError> {
+        let subjects = &self.subjects;
+        let tablename = &self.tablename;
+        let returning_columns = ctx.main_sqlo.to_non_null_columns();
+
+        let returning = if ctx.fetch.is_returning() {
+            format!(" RETURNING {}", returning_columns)
+        } else {
+            "".to_string()
+        };
+
+        let on_conflict = &self.on_conflict;
+
+        Ok(format!(
+            "INSERT INTO {tablename} {subjects}{on_conflict}{returning}"
+        ))
+    }
+
+    fn query_delete(&self, ctx: &Generator) -> Result<String, SqloError> {
+        let tablename = &self.tablename;
+        let where_query = &self.wwhere;
+        let returning_columns = ctx.main_sqlo.to_non_null_columns();
+
+        let returning = if ctx.fetch.is_returning() {
+            format!(" RETURNING {}", returning_columns)
+        } else {
+            "".to_string()
+        };
+
+        Ok(format!("DELETE FROM {tablename}{where_query}{returning}"))
+    }
+}
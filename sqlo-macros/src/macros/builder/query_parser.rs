@@ -1,16 +1,21 @@
 use darling::util::IdentString;
 
-use crate::macros::{Assigns, Clauses, Column};
+use crate::macros::{Assigns, Clauses, Column, Cte};
 
-use super::Fetch;
+use super::{Compound, Fetch, Recursive};
 
 pub trait QueryParser {
     #[cfg(debug_assertions)]
     fn debug(&self) -> bool;
     fn entity(&self) -> &IdentString;
     fn related(&self) -> &Option<IdentString>;
+    fn include(&self) -> Option<&IdentString>;
+    fn recursive(&self) -> Option<&Recursive>;
+    fn compound(&self) -> Option<&Compound>;
+    fn derived(&self) -> Option<&Cte>;
     fn columns(&self) -> &[Column];
     fn assigns(&self) -> &Assigns;
+    fn rows(&self) -> &[Assigns];
     fn custom_struct(&self) -> Option<IdentString>;
     fn pk_value(&self) -> PkValue;
     fn clauses(&self) -> &Clauses;
@@ -0,0 +1,95 @@
+use itertools::Itertools;
+
+use crate::{
+    error::SqloError,
+    macros::{kw, ColExpr, ColumnToSql, Fragment, Generator},
+    relations::Relation,
+};
+
+/// Name of the recursive cte which replaces the table in the main query.
+pub const TREE_TABLE: &str = "sqlo_tree";
+/// Column of the tree giving the distance to the queried row, starting at 1.
+/// Reserved so that it never shadows a field of the entity.
+pub const TREE_DEPTH: &str = "sqlo_depth";
+
+/// Tree traversal of a self join: `Entity[pk].related recursive depth 3`
+#[derive(Debug, Clone)]
+pub struct Recursive {
+    keyword: kw::recursive,
+    depth: ColExpr,
+}
+
+impl quote::ToTokens for Recursive {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.keyword.to_tokens(tokens);
+        let depth = &self.depth;
+        quote::quote! {depth #depth}.to_tokens(tokens);
+    }
+}
+
+impl syn::parse::Parse for Recursive {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let keyword = input.parse::<kw::recursive>()?;
+        // without a max depth, a cycle in the data would never end
+        if !input.peek(kw::depth) {
+            return Err(syn::Error::new_spanned(
+                keyword,
+                "`recursive` needs a max depth: `recursive depth 10`",
+            ));
+        }
+        input.parse::<kw::depth>()?;
+        let depth = input.parse()?;
+        Ok(Recursive { keyword, depth })
+    }
+}
+
+impl Recursive {
+    /// `WITH RECURSIVE` query of the rows reached from `pk` following `relation`:
+    /// toward parents if `ancestors` else toward children.
+    pub fn tree_to_sql(
+        &self,
+        relation: &Relation,
+        ancestors: bool,
+        pk: &syn::Expr,
+        ctx: &mut Generator,
+    ) -> Result<Fragment, SqloError> {
+        let sqlo = ctx.sqlos.get(&relation.from)?;
+        let tablename = sqlo.tablename.clone();
+        let pk_column = sqlo.pk_field()?.column.clone();
+        let fk_column = sqlo
+            .field(relation.field.as_ident())
+            .ok_or_else(|| SqloError::new_spanned(&relation.field, "no field found"))?
+            .column
+            .clone();
+        let columns = sqlo.fields.iter().map(|f| &f.column).join(", ");
+        let t_columns = sqlo
+            .fields
+            .iter()
+            .map(|f| format!("t.{}", f.column))
+            .join(", ");
+
+        let mut res = Fragment::from_expr(pk.clone(), ctx);
+        // the queried row is not part of the tree
+        let (anchor, on) = if ancestors {
+            (
+                format!(
+                    "{pk_column} = (SELECT {fk_column} FROM {tablename} WHERE {pk_column} = {})",
+                    res.query
+                ),
+                format!("t.{pk_column} = r.{fk_column}"),
+            )
+        } else {
+            (
+                format!("{fk_column} = {}", res.query),
+                format!("t.{fk_column} = r.{pk_column}"),
+            )
+        };
+        let depth = self.depth.column_to_sql(ctx)?;
+        let max_depth = depth.query.clone();
+        res = res.add_no_comma(depth);
+        res.query = format!(
+            "{TREE_TABLE} AS (SELECT {columns}, 1 AS {TREE_DEPTH} FROM {tablename} WHERE {anchor} UNION ALL SELECT {t_columns}, r.{TREE_DEPTH} + 1 FROM {tablename} t JOIN {TREE_TABLE} r ON {on} WHERE r.{TREE_DEPTH} < {max_depth})"
+        );
+        Ok(res)
+    }
+}
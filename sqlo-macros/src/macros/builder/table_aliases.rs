@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use darling::util::IdentString;
 
-use crate::{error::SqloError, relations::Relation, sqlos::Sqlos};
+use crate::{error::SqloError, sqlos::Sqlos};
 
 #[derive(Debug, Clone)]
 // sqlo_or_related_ident:(char alias, Sqlo ident)
@@ -18,6 +18,14 @@ impl<'a> TableAliases<'a> {
         }
     }
 
+    /// Same aliases, seen from a nested query with its own sqlos.
+    pub fn with_sqlos<'b>(&self, sqlos: &'b Sqlos) -> TableAliases<'b> {
+        TableAliases {
+            tables: self.tables.clone(),
+            sqlos,
+        }
+    }
+
     pub fn contains(&self, sqlo_or_related: &IdentString) -> bool {
         self.tables.contains_key(sqlo_or_related)
     }
@@ -38,15 +46,11 @@ impl<'a> TableAliases<'a> {
             .insert(sqlo.clone(), (self.get_next_alias(), sqlo.clone()));
     }
 
-    pub fn insert_related(&mut self, rel: &Relation) {
-        self.tables.insert(
-            rel.related.clone(),
-            (self.get_next_alias(), rel.from.clone()),
-        );
-    }
-    pub fn insert_related_alias(&mut self, rel: &Relation) {
-        if !&self.contains(&rel.related) {
-            self.insert_related(rel)
+    /// Alias `name` as a table of `sqlo` if not already present.
+    pub fn insert_alias(&mut self, name: &IdentString, sqlo: &IdentString) {
+        if !self.contains(name) {
+            self.tables
+                .insert(name.clone(), (self.get_next_alias(), sqlo.clone()));
         }
     }
 
@@ -1,7 +1,7 @@
 use crate::error::SqloError;
 use syn::parse::ParseStream;
 
-use super::{GroupBy, Having, Limit, OrderBy, Where};
+use super::{GroupBy, Having, Limit, OnConflict, OrderBy, Where, With};
 
 #[derive(Debug, Clone)]
 pub enum Clause {
@@ -10,6 +10,8 @@ pub enum Clause {
     Having(Having),
     Limit(Limit),
     OrderBy(OrderBy),
+    OnConflict(OnConflict),
+    With(With),
 }
 
 macro_rules! impl_from_from_clause {
@@ -24,7 +26,7 @@ macro_rules! impl_from_from_clause {
     };
 }
 
-impl_from_from_clause! { Where, GroupBy, Having, Limit, OrderBy }
+impl_from_from_clause! { Where, GroupBy, Having, Limit, OrderBy, OnConflict, With }
 
 #[derive(Debug, Clone)]
 pub struct Clauses(Vec<Clause>);
@@ -44,6 +46,20 @@ impl Clauses {
         Ok(())
     }
 
+    pub fn push(&mut self, clause: Clause) {
+        self.0.push(clause)
+    }
+
+    /// Remove `order_by` and `limit` clauses.
+    pub fn take_ordering(&mut self) -> Vec<Clause> {
+        let (ordering, others) = self
+            .0
+            .drain(..)
+            .partition(|c| matches!(c, Clause::OrderBy(_) | Clause::Limit(_)));
+        self.0 = others;
+        ordering
+    }
+
     pub fn iter(&self) -> std::slice::Iter<Clause> {
         self.0.iter()
     }
@@ -2,13 +2,17 @@ mod clause;
 mod group_by;
 mod having;
 mod limit;
+mod on_conflict;
 mod order_by;
 mod r#where;
+mod with;
 
 pub use clause::{Clause, Clauses};
 pub use group_by::GroupBy;
 pub use having::Having;
 
 pub use limit::Limit;
-pub use order_by::{OrderBy, OrderElem};
+pub use on_conflict::OnConflict;
+pub use order_by::{DynamicColumn, OrderBy, OrderElem};
 pub use r#where::Where;
+pub use with::{extend_sqlos, scoped_sqlos, Cte, With};
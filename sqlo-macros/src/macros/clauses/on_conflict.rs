@@ -0,0 +1,72 @@
+use darling::util::IdentString;
+use syn::{punctuated::Punctuated, Token};
+
+use crate::{
+    error::SqloError,
+    macros::{kw, Assigns, ColumnToSql, Context, Fragment, Generator},
+    methods::save::on_conflict_query,
+};
+
+#[derive(Debug, Clone)]
+pub enum ConflictAction {
+    Nothing,
+    Update(Assigns),
+}
+
+#[derive(Debug, Clone)]
+pub struct OnConflict {
+    target: Vec<IdentString>,
+    action: ConflictAction,
+}
+
+impl syn::parse::Parse for OnConflict {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::on_conflict>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let target = Punctuated::<syn::Ident, Token![,]>::parse_separated_nonempty(&content)?
+            .into_iter()
+            .map(IdentString::from)
+            .collect();
+        let action = if input.peek(kw::nothing) {
+            input.parse::<kw::nothing>()?;
+            ConflictAction::Nothing
+        } else if input.peek(kw::update) {
+            input.parse::<kw::update>()?;
+            ConflictAction::Update(input.parse()?)
+        } else {
+            return Err(input.error("expect `update` or `nothing` after `on_conflict(...)`"));
+        };
+        Ok(OnConflict { target, action })
+    }
+}
+
+impl ColumnToSql for OnConflict {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        let target = self
+            .target
+            .iter()
+            .map(|c| ctx.tables.column(&ctx.main_sqlo.ident, c))
+            .collect::<Result<Vec<_>, _>>()?;
+        let target = target.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut res = match &self.action {
+            ConflictAction::Nothing => on_conflict_query(&target, None).into(),
+            ConflictAction::Update(assigns) => {
+                let mut res = Fragment::default();
+                for assign in assigns.iter() {
+                    let mut lhs = assign.lhs.column_to_sql(ctx)?;
+                    lhs.append_str(" =");
+                    // existing row and proposed row are only available in rhs
+                    ctx.context.push(Context::OnConflict);
+                    let rhs = assign.rhs.column_to_sql(ctx)?;
+                    ctx.context.pop();
+                    res = res + lhs.add_no_comma(rhs);
+                }
+                res.query = on_conflict_query(&target, Some(&res.query));
+                res
+            }
+        };
+        res.prepend_str(" ");
+        Ok(res)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    error::SqloError,
+    macros::{kw, ColExpr, ColumnToSql, Context, Fragment, Generator},
+};
+
+use darling::util::IdentString;
+use syn::{punctuated::Punctuated, Expr, Token};
+
+/// Placeholder of the dynamic column in the query, replaced at expansion.
+pub const DYNAMIC_COLUMN: &str = "{sqlo_dynamic_column}";
+
+/// `order_by ::sort_col` where `sort_col` is a value of the generated column enum.
+///
+/// Each variant gets its own checked query, the column is never a bound parameter.
+#[derive(Debug, Clone)]
+pub struct DynamicColumn {
+    pub expr: Expr,
+    pub columns: Vec<(IdentString, String)>, // (enum variant, sql column)
+}
+
+impl DynamicColumn {
+    /// Every possible query, by enum variant.
+    pub fn queries(&self, query: &str) -> Vec<(&IdentString, String)> {
+        self.columns
+            .iter()
+            .map(|(variant, column)| (variant, query.replace(DYNAMIC_COLUMN, column)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderElem {
+    column: ColExpr,
+    sens: bool,
+}
+
+impl syn::parse::Parse for OrderElem {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let sens = if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            false
+        } else {
+            true
+        };
+        let column = input.parse::<ColExpr>()?;
+        match column {
+            ColExpr::Call(_) | ColExpr::Field(_) | ColExpr::Ident(_) | ColExpr::Value(_) => {
+                Ok(OrderElem { column, sens })
+            }
+            _ => Err(syn::Error::new_spanned(
+                column,
+                "order_by only supports identifier, related identifier, function or column enum value",
+            )),
+        }
+    }
+}
+
+impl quote::ToTokens for OrderElem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let colexpr = &self.column;
+        if self.sens {
+            quote::quote! {order_by #colexpr}
+        } else {
+            quote::quote! { order_by -#colexpr }
+        }
+        .to_tokens(tokens);
+    }
+}
+
+impl ColumnToSql for OrderElem {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, crate::error::SqloError> {
+        let sens = if self.sens { "" } else { " DESC" };
+        let mut res = if let ColExpr::Value(expr) = &self.column {
+            dynamic_column_to_sql(expr, ctx)?
+        } else {
+            self.column.column_to_sql(ctx)?
+        };
+        res.append_str(sens);
+        Ok(res)
+    }
+}
+
+fn dynamic_column_to_sql(expr: &Expr, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+    if ctx.context.contains(&Context::SubQuery) || ctx.include.is_some() {
+        return Err(SqloError::new_spanned(
+            expr,
+            "column enum in order_by is not supported in subquery or with include",
+        ));
+    }
+    if ctx.dynamic_column.is_some() {
+        return Err(SqloError::new_spanned(
+            expr,
+            "only one column enum is supported by order_by",
+        ));
+    }
+    let main_sqlo = ctx.main_sqlo;
+    let mut columns = vec![];
+    for field in &main_sqlo.fields {
+        let variant = heck::AsUpperCamelCase(field.ident.as_str()).to_string();
+        columns.push((
+            IdentString::new(syn::Ident::new(&variant, field.ident.span())),
+            ctx.tables
+                .alias_dot_column(&main_sqlo.ident, &field.ident)?,
+        ));
+    }
+    ctx.dynamic_column = Some(DynamicColumn {
+        expr: expr.clone(),
+        columns,
+    });
+    Ok(DYNAMIC_COLUMN.to_string().into())
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderBy(Punctuated<OrderElem, Token![,]>);
+
+impl syn::parse::Parse for OrderBy {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::order_by>()?;
+        let reste;
+        parse_possible_bracketed!(input, reste);
+        Ok(OrderBy(Punctuated::parse_separated_nonempty(reste)?))
+    }
+}
+
+impl quote::ToTokens for OrderBy {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.0.to_tokens(tokens);
+    }
+}
+
+impl OrderBy {
+    /// Order by position of the selected `columns`, as needed by a combined query.
+    pub fn positions_to_sql(&self, columns: &[IdentString]) -> Result<Fragment, SqloError> {
+        let mut res = vec![];
+        for elem in &self.0 {
+            let position = match &elem.column {
+                ColExpr::Ident(ident) => columns.iter().position(|c| c == ident),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                SqloError::new_spanned(
+                    &elem.column,
+                    "order_by of a combined query only supports selected columns",
+                )
+            })?;
+            let sens = if elem.sens { "" } else { " DESC" };
+            res.push(format!("{}{sens}", position + 1));
+        }
+        Ok(format!(" ORDER BY {}", res.join(", ")).into())
+    }
+}
+
+impl ColumnToSql for OrderBy {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, crate::error::SqloError> {
+        ctx.context.push(Context::OrderBy);
+        let mut res = self.0.iter().fold(
+            Ok(Fragment::default()),
+            |acc: Result<Fragment, SqloError>, nex| Ok(acc.unwrap() + nex.column_to_sql(ctx)?), //unwrap ok can't be None
+        )?;
+        res.prepend_str(" ORDER BY ");
+        ctx.context.pop();
+        Ok(res)
+    }
+}
@@ -0,0 +1,242 @@
+use std::borrow::Cow;
+
+use darling::util::IdentString;
+use itertools::Itertools;
+use syn::{braced, punctuated::Punctuated, Token};
+
+use crate::{
+    error::SqloError,
+    field::Field,
+    macros::{
+        kw, AliasCast, Clause, ColExpr, Column, ColumnCast, ColumnToSql, Fragment, Generator, Mode,
+        QueryParser, SelectParser, TableAliases,
+    },
+    sqlo::Sqlo,
+    sqlos::Sqlos,
+};
+
+/// Common table expressions: `with name = {Entity ...}, other = {...}`
+#[derive(Debug, Clone)]
+pub struct With(Punctuated<Cte, Token![,]>);
+
+#[derive(Debug, Clone)]
+pub struct Cte {
+    name: IdentString,
+    query: SelectParser,
+}
+
+impl syn::parse::Parse for With {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::with>()?;
+        let mut ctes = Punctuated::new();
+        loop {
+            ctes.push_value(input.parse()?);
+            // a comma followed by `name =` is another cte, otherwise it belongs to the main query
+            if input.peek(Token![,]) && input.peek2(syn::Ident) && input.peek3(Token![=]) {
+                ctes.push_punct(input.parse()?);
+            } else {
+                break;
+            }
+        }
+        Ok(With(ctes))
+    }
+}
+
+impl syn::parse::Parse for Cte {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<syn::Ident>()?.into();
+        input.parse::<Token![=]>()?;
+        let content;
+        braced!(content in input);
+        Ok(Cte {
+            name,
+            query: content.parse()?,
+        })
+    }
+}
+
+impl With {
+    /// Add every cte as a pseudo sqlo, so that it can be queried like an entity.
+    pub fn extend_sqlos(&self, sqlos: &mut Sqlos) -> Result<(), SqloError> {
+        for cte in &self.0 {
+            cte.extend_sqlos(sqlos)?;
+        }
+        Ok(())
+    }
+}
+
+impl ColumnToSql for With {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        let mut queries = vec![];
+        for cte in &self.0 {
+            queries.push(format!("{} AS ({})", cte.name, cte.query_to_sql(ctx)?));
+        }
+        // `WITH` is added by the query builder with the other ctes
+        Ok(queries.iter().join(", ").into())
+    }
+}
+
+impl Cte {
+    /// Derived table: `{Entity ...} as name`
+    pub fn parse_derived(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+        let query = content.parse()?;
+        input.parse::<Token![as]>().map_err(|_| {
+            input.error("a subquery in place of a sqlo struct needs an alias: `{...} as name`")
+        })?;
+        Ok(Cte {
+            name: input.parse::<syn::Ident>()?.into(),
+            query,
+        })
+    }
+
+    pub fn name(&self) -> &IdentString {
+        &self.name
+    }
+
+    /// Add the named query as a pseudo sqlo.
+    pub fn extend_sqlos(&self, sqlos: &mut Sqlos) -> Result<(), SqloError> {
+        if sqlos.get(&self.name).is_ok() {
+            return Err(SqloError::new_spanned(
+                &self.name,
+                format!("`{}` is already a Sqlo struct", self.name),
+            ));
+        }
+        let sqlo = query_as_sqlo(&self.name, &self.query, sqlos)?;
+        sqlos.entities.push(sqlo);
+        Ok(())
+    }
+
+    // the query has its own scope: table aliases start again
+    pub fn query_to_sql(&self, ctx: &mut Generator) -> Result<String, SqloError> {
+        let sqlos = scoped_sqlos(&self.query, ctx.sqlos)?;
+        let generator = Generator::from_sqlo_query_parse(
+            Mode::Select,
+            self.query.clone(),
+            &sqlos,
+            true,
+            TableAliases::new(&sqlos),
+        )?;
+        let qr: Fragment = generator.try_into()?;
+        Ok(ctx.arguments.merge(&qr.query, &qr.params))
+    }
+}
+
+/// Add every cte and the derived table of `query` as pseudo sqlos.
+pub fn extend_sqlos<T: QueryParser>(query: &T, sqlos: &mut Sqlos) -> Result<(), SqloError> {
+    for clause in query.clauses().iter() {
+        if let Clause::With(with) = clause {
+            with.extend_sqlos(sqlos)?;
+        }
+    }
+    if let Some(derived) = query.derived() {
+        derived.extend_sqlos(sqlos)?;
+    }
+    Ok(())
+}
+
+/// Sqlos seen by a nested query: its own ctes and derived table only live in its scope.
+pub fn scoped_sqlos<'a, T: QueryParser>(
+    query: &T,
+    sqlos: &'a Sqlos,
+) -> Result<Cow<'a, Sqlos>, SqloError> {
+    let with = query.clauses().iter().any(|c| matches!(c, Clause::With(_)));
+    if !with && query.derived().is_none() {
+        return Ok(Cow::Borrowed(sqlos));
+    }
+    let mut scoped = sqlos.clone();
+    extend_sqlos(query, &mut scoped)?;
+    Ok(Cow::Owned(scoped))
+}
+
+/// Pseudo sqlo whose fields are the columns selected by `query`.
+pub fn query_as_sqlo(
+    name: &IdentString,
+    query: &SelectParser,
+    sqlos: &Sqlos,
+) -> Result<Sqlo, SqloError> {
+    let sqlos = scoped_sqlos(query, sqlos)?;
+    let entity = Generator::get_main_sqlo(query, &sqlos)?;
+    let fields = if query.columns().is_empty() {
+        entity.fields.iter().map(pseudo_field).collect()
+    } else {
+        query
+            .columns()
+            .iter()
+            .map(|c| column_as_field(c, entity))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    Ok(Sqlo {
+        ident: name.clone(),
+        fields,
+        tablename: name.to_string(),
+        pk_fields: vec![],
+        parse_only: true,
+        all_columns_as_query: String::new(),
+        many_to_many: vec![],
+        pseudo: true,
+    })
+}
+
+// keep name and type of a selected field, but nothing of its relations
+fn pseudo_field(field: &Field) -> Field {
+    Field {
+        ident: field.ident.clone(),
+        ty: field.ty.clone(),
+        column: field.column.clone(),
+        type_override: field.type_override,
+        primary_key: false,
+        insert_fn: None,
+        fk: None,
+        related: None,
+        parent: None,
+    }
+}
+
+fn column_as_field(column: &Column, entity: &Sqlo) -> Result<Field, SqloError> {
+    match column {
+        Column::Mono(ColExpr::Ident(ident)) => entity
+            .field(ident.as_ident())
+            .map(pseudo_field)
+            .ok_or_else(|| {
+                SqloError::new_spanned(
+                    ident,
+                    format!("SqlorFieldError: no field {} in {}", ident, entity.ident),
+                )
+            }),
+        Column::Cast(ColumnCast {
+            expr,
+            alias: AliasCast::Ident(alias),
+        }) => {
+            let ty = match expr {
+                ColExpr::Ident(ident) if entity.field(ident.as_ident()).is_some() => {
+                    entity.field(ident.as_ident()).unwrap().ty.clone()
+                }
+                _ => syn::parse_quote! {Option<_>},
+            };
+            Ok(Field {
+                ident: alias.clone(),
+                ty,
+                column: alias.to_string(),
+                type_override: false,
+                primary_key: false,
+                insert_fn: None,
+                fk: None,
+                related: None,
+                parent: None,
+            })
+        }
+        Column::Cast(ColumnCast {
+            alias: AliasCast::Literal(alias),
+            ..
+        }) => Err(SqloError::new_spanned(
+            alias,
+            "alias of a column used as field must be a plain identifier",
+        )),
+        Column::Mono(expr) => Err(SqloError::new_spanned(
+            expr,
+            "column used as field must be a field of the sqlo struct or be aliased with `as`",
+        )),
+    }
+}
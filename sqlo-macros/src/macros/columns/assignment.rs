@@ -20,6 +20,13 @@ impl syn::parse::Parse for Assign {
     }
 }
 
+impl quote::ToTokens for Assign {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Assign { lhs, rhs } = self;
+        quote::quote! {#lhs = #rhs}.to_tokens(tokens)
+    }
+}
+
 impl ColumnToSql for &Assign {
     fn column_to_sql(
         &self,
@@ -44,6 +51,13 @@ impl syn::parse::Parse for Assigns {
     }
 }
 
+impl quote::ToTokens for Assigns {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let assigns = &self.0;
+        quote::quote! {#(#assigns),*}.to_tokens(tokens)
+    }
+}
+
 impl ColumnToSql for Assigns {
     fn column_to_sql(
         &self,
@@ -67,4 +81,8 @@ impl Assigns {
         }
         None
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Assign> {
+        self.0.iter()
+    }
 }
@@ -6,11 +6,12 @@ use syn::{
 
 use crate::{
     error::SqloError,
-    macros::{unarize, ColumnToSql, Fragment, Generator, Operator},
+    macros::{next_is_not_a_keyword, unarize, ColumnToSql, Fragment, Generator, Operator},
 };
 
 use super::{
-    ColExprCall, ColExprCase, ColExprField, ColExprOp, ColExprParen, ColExprSubSelect, ColExprUnary,
+    ColExprCall, ColExprCase, ColExprField, ColExprMethod, ColExprOp, ColExprParen, ColExprRange,
+    ColExprSubSelect, ColExprUnary,
 };
 
 #[derive(Debug, Clone)]
@@ -21,8 +22,11 @@ pub enum ColExpr {
     Field(ColExprField),
     Literal(Lit),
     Value(Expr),
+    Method(ColExprMethod),
+    Optional(Expr), // `?::value`, only as right operand, see ColExprOp
     Operation(ColExprOp),
     Paren(ColExprParen),
+    Range(ColExprRange),
     SubSelect(ColExprSubSelect),
     Unary(ColExprUnary),
     Asterisk,
@@ -37,9 +41,12 @@ impl quote::ToTokens for ColExpr {
             Self::Call(c) => c.to_tokens(tokens),
             Self::Literal(l) => l.to_tokens(tokens),
             Self::Value(e) => e.to_tokens(tokens),
+            Self::Method(m) => m.to_tokens(tokens),
+            Self::Optional(e) => quote::quote! {?::#e}.to_tokens(tokens),
             Self::Operation(o) => o.to_tokens(tokens),
             Self::Asterisk => "*".to_tokens(tokens),
             Self::Paren(p) => p.to_tokens(tokens),
+            Self::Range(r) => r.to_tokens(tokens),
             Self::SubSelect(s) => s.to_tokens(tokens),
             Self::Unary(p) => p.to_tokens(tokens),
         }
@@ -48,27 +55,67 @@ impl quote::ToTokens for ColExpr {
 
 impl syn::parse::Parse for ColExpr {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let unary = ColExprUnary::get_next_unary(input)?;
-        let initial_col = if input.peek(syn::token::Paren) {
-            input.parse::<ColExprParen>()?.into()
+        let col = if input.peek(Token![..]) {
+            // range without start: `..end`
+            parse_range(input, None)?
         } else {
-            parse_initial(input)?
+            let unarized_col = parse_unarized(input)?;
+            if input.peek(Token![..]) {
+                parse_range(input, Some(unarized_col))?
+            } else {
+                unarized_col
+            }
         };
-        let unarized_col = unarize(initial_col, unary);
         if Operator::next_is_supported_op(&input) {
-            parse_operation(input, unarized_col)
+            parse_operation(input, col)
         } else {
-            Ok(unarized_col)
+            Ok(col)
         }
     }
 }
 
+fn parse_unarized(input: syn::parse::ParseStream) -> syn::Result<ColExpr> {
+    let unary = ColExprUnary::get_next_unary(input)?;
+    let initial_col = if input.peek(syn::token::Paren) {
+        input.parse::<ColExprParen>()?.into()
+    } else {
+        parse_initial(input)?
+    };
+    let col = ColExprMethod::parse_chain(input, initial_col)?;
+    Ok(unarize(col, unary))
+}
+
+// `start..end`, `start..=end`, `start..` or `..end`
+fn parse_range(input: syn::parse::ParseStream, start: Option<ColExpr>) -> syn::Result<ColExpr> {
+    let limits = input.parse::<syn::RangeLimits>()?;
+    let has_end = input.peek(Lit)
+        || input.peek(Token![::])
+        || input.peek(Token![-])
+        || input.peek(syn::token::Paren)
+        || (input.peek(syn::Ident) && next_is_not_a_keyword(&input));
+    let end = if has_end {
+        Some(Box::new(parse_unarized(input)?))
+    } else {
+        None
+    };
+    if end.is_none() && (start.is_none() || matches!(limits, syn::RangeLimits::Closed(_))) {
+        return Err(input.error("range needs an end"));
+    }
+    Ok(ColExpr::Range(ColExprRange {
+        start: start.map(Box::new),
+        limits,
+        end,
+    }))
+}
+
 fn parse_initial(input: syn::parse::ParseStream) -> syn::Result<ColExpr> {
     let res = if input.peek(syn::Ident) {
         // let start to see if it starts with an Ident
         let fork = input.fork();
         fork.parse::<syn::Ident>()?;
-        if fork.peek(Token![.]) || (fork.peek(Token![=]) && fork.peek2(Token![.])) {
+        if (fork.peek(Token![.]) && !ColExprMethod::next_is_method(&&fork))
+            || (fork.peek(Token![=]) && fork.peek2(Token![.]))
+        {
             //parse joins as field: base.member or base=.member
             ColExprField::parse(input)?.into()
         } else if fork.peek(syn::token::Paren) {
@@ -106,12 +153,45 @@ fn parse_initial(input: syn::parse::ParseStream) -> syn::Result<ColExpr> {
 
 fn parse_operation(input: syn::parse::ParseStream, lhs: ColExpr) -> syn::Result<ColExpr> {
     let op = input.parse::<Operator>()?;
+    if input.peek(Token![?]) && input.peek2(Token![::]) {
+        // optional filter: `lhs op ?::value` is kept together, then parsing goes on.
+        input.parse::<Token![?]>()?;
+        input.parse::<Token![::]>()?;
+        let optional = ColExpr::Operation(ColExprOp {
+            lhs: Box::new(lhs),
+            op,
+            rhs: Box::new(ColExpr::Optional(parse_supported_expr(&input)?)),
+        });
+        return if Operator::next_is_supported_op(&input) {
+            parse_operation(input, optional)
+        } else {
+            Ok(optional)
+        };
+    }
     let rhs = input.parse::<ColExpr>()?;
-    Ok(ColExpr::Operation(ColExprOp {
-        lhs: Box::new(lhs),
-        op,
-        rhs: Box::new(rhs),
-    }))
+    Ok(regroup_operation(lhs, op, rhs))
+}
+
+// `a op b && c` is kept as `(a op b) && c` so `a op b` is seen as a whole,
+// as is `a op b > ?::c`: the whole `a op b` is the optional's left operand.
+fn regroup_operation(lhs: ColExpr, op: Operator, rhs: ColExpr) -> ColExpr {
+    match rhs {
+        ColExpr::Operation(rhs)
+            if !op.is_logical()
+                && (rhs.op.is_logical() || matches!(*rhs.rhs, ColExpr::Optional(_))) =>
+        {
+            ColExpr::Operation(ColExprOp {
+                lhs: Box::new(regroup_operation(lhs, op, *rhs.lhs)),
+                op: rhs.op,
+                rhs: rhs.rhs,
+            })
+        }
+        rhs => ColExpr::Operation(ColExprOp {
+            lhs: Box::new(lhs),
+            op,
+            rhs: Box::new(rhs),
+        }),
+    }
 }
 
 impl ColumnToSql for ColExpr {
@@ -123,9 +203,15 @@ impl ColumnToSql for ColExpr {
             Self::Field(col_expr_field) => col_expr_field.column_to_sql(ctx),
             Self::Literal(l) => l.column_to_sql(ctx),
             Self::Value(expr_value) => expr_value.column_to_sql(ctx),
+            Self::Method(m) => m.column_to_sql(ctx),
+            Self::Optional(expr) => Err(SqloError::new_spanned(
+                expr,
+                "`?::` is only supported as right operand of a where condition",
+            )),
             Self::Operation(expr_op) => expr_op.column_to_sql(ctx),
             Self::Asterisk => Ok("*".to_string().into()),
             Self::Paren(p) => p.column_to_sql(ctx),
+            Self::Range(r) => r.column_to_sql(ctx),
             Self::SubSelect(p) => p.column_to_sql(ctx),
             Self::Unary(p) => p.column_to_sql(ctx),
         }
@@ -141,9 +227,15 @@ impl ColumnToSql for &ColExpr {
             ColExpr::Field(col_expr_field) => col_expr_field.column_to_sql(ctx),
             ColExpr::Literal(l) => l.column_to_sql(ctx),
             ColExpr::Value(expr_value) => expr_value.column_to_sql(ctx),
+            ColExpr::Method(m) => m.column_to_sql(ctx),
+            ColExpr::Optional(expr) => Err(SqloError::new_spanned(
+                expr,
+                "`?::` is only supported as right operand of a where condition",
+            )),
             ColExpr::Operation(expr_op) => expr_op.column_to_sql(ctx),
             ColExpr::Asterisk => Ok("*".to_string().into()),
             ColExpr::Paren(p) => p.column_to_sql(ctx),
+            ColExpr::Range(r) => r.column_to_sql(ctx),
             ColExpr::SubSelect(p) => p.column_to_sql(ctx),
             ColExpr::Unary(p) => p.column_to_sql(ctx),
         }
@@ -168,8 +260,10 @@ impl_from_variant_for_colexpr!(
     Field ColExprField,
     Literal Lit,
     Value Expr,
+    Method ColExprMethod,
     Operation ColExprOp,
     Paren ColExprParen,
+    Range ColExprRange,
     SubSelect ColExprSubSelect,
     Unary ColExprUnary,
     Case ColExprCase
@@ -5,18 +5,20 @@ use crate::{
     SqloError,
 };
 
-use super::ColExprParen;
+use super::{ColExprParen, Window};
 
 #[derive(Debug, Clone)]
 pub struct ColExprCall {
     pub base: IdentString,
     pub args: ColExprParen,
+    pub over: Option<Window>,
 }
 
 impl quote::ToTokens for ColExprCall {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         self.base.to_tokens(tokens);
         self.args.to_tokens(tokens);
+        self.over.to_tokens(tokens);
     }
 }
 
@@ -37,15 +39,22 @@ impl ColumnToSql for ColExprCall {
         let mut res = self.args.column_to_sql(ctx)?;
         ctx.context.pop();
         res.prepend_str(self.base.as_str());
+        if let Some(over) = &self.over {
+            res = res.add_no_comma(over.column_to_sql(ctx)?);
+        }
         Ok(res)
     }
 }
 
 impl syn::parse::Parse for ColExprCall {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Ok(ColExprCall {
-            base: input.parse::<syn::Ident>()?.into(),
-            args: input.parse::<ColExprParen>()?,
-        })
+        let base = input.parse::<syn::Ident>()?.into();
+        let args = ColExprParen::parse_call_args(input)?;
+        let over = if Window::next_is_window(&input) {
+            Some(input.parse::<Window>()?)
+        } else {
+            None
+        };
+        Ok(ColExprCall { base, args, over })
     }
 }
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{punctuated::Punctuated, Token};
 
-use crate::macros::ColumnToSql;
+use crate::macros::{ColumnToSql, Fragment};
 
 use super::ColExpr;
 
@@ -46,9 +46,20 @@ impl ColumnToSql for ColExprCase {
         } else {
             "".into()
         };
+        // ranges can't be compared in `CASE col WHEN`: use `CASE WHEN col ...`
+        let subject = if self.case.is_some()
+            && self
+                .arms
+                .iter()
+                .any(|arm| matches!(arm.lhs, ColExpr::Range(_)))
+        {
+            Some(std::mem::take(&mut res))
+        } else {
+            None
+        };
         res.prepend_str("CASE ");
         for arm in &self.arms {
-            res = res.add_no_comma(arm.column_to_sql(ctx)?);
+            res = res.add_no_comma(arm.to_sql(subject.as_ref(), ctx)?);
         }
         res.append_str(" END");
         Ok(res)
@@ -85,11 +96,13 @@ impl ToTokens for Arm {
     }
 }
 
-impl ColumnToSql for Arm {
-    fn column_to_sql(
+impl Arm {
+    // with a subject, arm is rendered as a condition on it.
+    fn to_sql(
         &self,
+        subject: Option<&Fragment>,
         ctx: &mut crate::macros::Generator,
-    ) -> Result<crate::macros::Fragment, crate::error::SqloError> {
+    ) -> Result<Fragment, crate::error::SqloError> {
         if let ColExpr::Ident(ref i) = self.lhs {
             if i.as_str() == "_" {
                 let mut res = self.rhs.column_to_sql(ctx)?;
@@ -97,7 +110,16 @@ impl ColumnToSql for Arm {
                 return Ok(res);
             }
         }
-        let mut res = self.lhs.column_to_sql(ctx)?;
+        let mut res = match (subject, &self.lhs) {
+            (Some(subject), ColExpr::Range(range)) => {
+                range.condition_to_sql(subject.clone(), ctx)?
+            }
+            (Some(subject), lhs) => subject
+                .clone()
+                .add_no_comma("=".into())
+                .add_no_comma(lhs.column_to_sql(ctx)?),
+            (None, lhs) => lhs.column_to_sql(ctx)?,
+        };
         res.prepend_str("WHEN ");
         res.append_str(" THEN");
         res = res.add_no_comma(self.rhs.column_to_sql(ctx)?);
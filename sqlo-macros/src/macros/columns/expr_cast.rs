@@ -27,18 +27,10 @@ impl ColumnToSql for ColumnCast {
                 Fragment::from(format!(" as {ident}"))
             }
             AliasCast::Literal(litstr) => {
-                let re = regex_macro::regex!(r#"^(\w+)[?!]?(?::\w+(?:::\w+)*)?$"#);
-                let alias_str = &litstr.value();
-                if let Some(captures) = re.captures(alias_str) {
-                    if let Some(alias) = captures.get(1) {
-                        let ident: IdentString =
-                            syn::Ident::new(alias.as_str(), litstr.span()).into();
-                        let formated_alias_string = format!("\"{alias_str}\"");
-                        ctx.aliases.insert(ident, expr.query.clone());
-                        Fragment::from(format!(" as {formated_alias_string}"))
-                    } else {
-                        return Err(SqloError::new_spanned(litstr, "invalid alias format"));
-                    }
+                if let Some(ident) = self.alias.name() {
+                    let formated_alias_string = format!("\"{}\"", litstr.value());
+                    ctx.aliases.insert(ident, expr.query.clone());
+                    Fragment::from(format!(" as {formated_alias_string}"))
                 } else {
                     return Err(SqloError::new_spanned(litstr, "invalid alias format"));
                 }
@@ -56,6 +48,30 @@ pub enum AliasCast {
     Literal(LitStr),
 }
 
+impl AliasCast {
+    /// Name of the alias without sqlx overrides: `n` for `"n!:i64"`.
+    pub fn name(&self) -> Option<IdentString> {
+        match self {
+            AliasCast::Ident(ident) => Some(ident.clone()),
+            AliasCast::Literal(litstr) => {
+                let re = regex_macro::regex!(r#"^(\w+)[?!]?(?::\w+(?:::\w+)*)?$"#);
+                re.captures(&litstr.value())
+                    .and_then(|captures| captures.get(1))
+                    .map(|alias| syn::Ident::new(alias.as_str(), litstr.span()).into())
+            }
+        }
+    }
+}
+
+impl quote::ToTokens for AliasCast {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            AliasCast::Ident(ident) => ident.to_tokens(tokens),
+            AliasCast::Literal(litstr) => litstr.to_tokens(tokens),
+        }
+    }
+}
+
 impl From<&syn::Ident> for AliasCast {
     fn from(ident: &syn::Ident) -> Self {
         AliasCast::Ident(IdentString::new(ident.clone()))
@@ -0,0 +1,194 @@
+use darling::util::IdentString;
+use indexmap::IndexSet;
+use proc_macro2::{Punct, Spacing};
+use quote::format_ident;
+use syn::Token;
+
+use crate::{
+    database::excluded_column,
+    error::SqloError,
+    macros::{ColExprMethod, ColumnToSql, Context, Fragment, Generator, Mode},
+    relations::Join,
+};
+
+#[derive(Debug, Clone)]
+pub struct ColExprField {
+    path: Vec<(IdentString, Join)>, // every relation hop: base.hop.member
+    member: IdentString,
+}
+
+impl ColExprField {
+    pub fn new(base: syn::Ident, member: syn::Ident, join: Join) -> Self {
+        Self {
+            path: vec![(base.into(), join)],
+            member: member.into(),
+        }
+    }
+
+    pub fn member(&self) -> &IdentString {
+        &self.member
+    }
+
+    fn base(&self) -> &IdentString {
+        &self.path[0].0
+    }
+}
+
+impl quote::ToTokens for ColExprField {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        use quote::TokenStreamExt;
+        for (hop, _) in &self.path {
+            hop.to_tokens(tokens);
+            tokens.append(Punct::new('.', Spacing::Joint));
+        }
+        self.member.to_tokens(tokens);
+    }
+}
+
+impl ColumnToSql for ColExprField {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        // value proposed for insertion in upsert: excluded.column
+        if ctx.context.contains(&Context::OnConflict) && *self.base() == "excluded" {
+            let column = ctx.tables.column(&ctx.main_sqlo.ident, &self.member)?;
+            return Ok(excluded_column(&column).into());
+        }
+        ctx.context.push(Context::Field);
+        let res = self.path_to_sql(ctx);
+        ctx.context.pop();
+        res
+    }
+}
+
+impl ColExprField {
+    // Follow each hop from the main sqlo, every hop joins a new aliased table.
+    fn path_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        let mut joins = IndexSet::new();
+        let mut source = ctx.main_sqlo.ident.clone();
+        let mut sqlo = ctx.main_sqlo.ident.clone();
+        for (idx, (hop, join)) in self.path.iter().enumerate() {
+            // first hop keeps its name as alias key so it can be shared with other expressions
+            let target: IdentString = if idx == 0 {
+                hop.clone()
+            } else {
+                format_ident!("{}__{}", source.as_ident(), hop.as_ident()).into()
+            };
+            match self.relation_join(&sqlo, &source, hop, &target, *join, ctx)? {
+                // update and delete have no FROM clause to hold the join
+                Some(_) if !matches!(ctx.mode, Mode::Select) => {
+                    return Err(SqloError::new_spanned(
+                        hop,
+                        "relation fields are only supported by select!, use a subquery instead",
+                    ))
+                }
+                Some((join, next_sqlo)) => {
+                    joins.insert(join);
+                    source = target;
+                    sqlo = next_sqlo;
+                }
+                // path starting from a sqlo ident or an already aliased table
+                None if idx == 0 && self.path.len() > 1 && ctx.tables.contains(hop) => {
+                    sqlo = ctx.tables.get(hop)?.1.clone();
+                    source = hop.clone();
+                }
+                None if idx == 0 => return self.not_a_relation(ctx),
+                None => {
+                    return Err(SqloError::new_spanned(
+                        hop,
+                        format!("No relation `{}` found for `{}`", hop, sqlo),
+                    ))
+                }
+            }
+        }
+        Ok(Fragment {
+            query: ctx.tables.alias_dot_column(&source, &self.member)?,
+            joins,
+            ..Default::default()
+        })
+    }
+
+    // Join needed if hop is a relation of sqlo: children, parent or many to many.
+    // Returns the join and the sqlo reached.
+    fn relation_join(
+        &self,
+        sqlo: &IdentString,
+        source: &IdentString,
+        hop: &IdentString,
+        target: &IdentString,
+        join: Join,
+        ctx: &mut Generator,
+    ) -> Result<Option<(String, IdentString)>, SqloError> {
+        if let Ok(rel) = ctx.sqlos.get_relation(sqlo, hop) {
+            return Ok(Some((
+                rel.to_join(source, target, join, ctx)?,
+                rel.from.clone(),
+            )));
+        }
+        if let Ok(rel) = ctx.sqlos.get_parent_relation(sqlo, hop) {
+            return Ok(Some((
+                rel.to_parent_join(source, target, join, ctx)?,
+                rel.to.clone(),
+            )));
+        }
+        if let Ok(rel) = ctx.sqlos.get_many_to_many(sqlo, hop) {
+            return Ok(Some((
+                rel.to_join(source, target, join, ctx)?,
+                rel.to.clone(),
+            )));
+        }
+        Ok(None)
+    }
+
+    // no relation then it could be a sqlo ident
+    fn not_a_relation(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        let column = if self.path.len() == 1 {
+            ctx.tables.alias_dot_column(self.base(), &self.member)
+        } else {
+            // aliased tables are already handled as path start
+            Err(SqloError::new_spanned(
+                self.base(),
+                "Sqlo: Invalid alias or identifier",
+            ))
+        };
+        match column {
+            Ok(res) => Ok(res.into()),
+            Err(err) => {
+                // left join (=.) can't be value, we buble up field error (if base is sqlo or a related fk)
+                if self.path.iter().any(|(_, join)| matches!(join, Join::Left))
+                    || err.msg().contains("SqloFieldError")
+                {
+                    // we track error content because non error variant
+                    Err(err)
+                } else {
+                    // inner join(.) but base is unknown so we use it as Value
+                    let expr: syn::Expr = syn::parse_quote!(#self);
+                    Ok(Fragment::from_expr(expr, ctx))
+                }
+            }
+        }
+    }
+}
+
+impl syn::parse::Parse for ColExprField {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut path = vec![];
+        loop {
+            let ident = input.parse::<syn::Ident>()?;
+            let join = if input.peek(Token![=]) && input.peek2(Token![.]) {
+                input.parse::<Token![=]>()?;
+                Join::Left
+            } else if input.peek(Token![.]) && !ColExprMethod::next_is_method(&input) {
+                Join::Inner
+            } else if !path.is_empty() {
+                // no more hop: it's the member
+                return Ok(ColExprField {
+                    path,
+                    member: ident.into(),
+                });
+            } else {
+                return Err(input.error("expected `.` or `=.`"));
+            };
+            input.parse::<Token![.]>()?;
+            path.push((ident.into(), join));
+        }
+    }
+}
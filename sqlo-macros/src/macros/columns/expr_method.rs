@@ -0,0 +1,170 @@
+use darling::util::IdentString;
+use itertools::Itertools;
+use syn::{parenthesized, punctuated::Punctuated, Lit, LitStr, Token};
+
+use crate::{
+    database::{concat, length, pattern_match, PATTERN_ESCAPES, PATTERN_WILDCARD},
+    error::SqloError,
+    macros::{ColumnToSql, Context, Fragment, Generator},
+};
+
+use super::ColExpr;
+
+const SUPPORTED_METHODS: &str = "trim, trim_start, trim_end, to_lowercase, to_uppercase, len, abs, round, unwrap_or, starts_with, ends_with, contains";
+
+#[derive(Debug, Clone)]
+pub struct ColExprMethod {
+    pub receiver: Box<ColExpr>,
+    pub method: IdentString,
+    pub args: Punctuated<ColExpr, Token![,]>,
+}
+
+impl quote::ToTokens for ColExprMethod {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let receiver = &self.receiver;
+        let method = &self.method;
+        let args = &self.args;
+        quote::quote! {#receiver.#method(#args)}.to_tokens(tokens);
+    }
+}
+
+impl ColExprMethod {
+    pub fn next_is_method(input: &syn::parse::ParseStream) -> bool {
+        input.peek(Token![.]) && input.peek2(syn::Ident) && input.peek3(syn::token::Paren)
+    }
+
+    // chained methods: receiver.method(args).method(args)
+    pub fn parse_chain(input: syn::parse::ParseStream, receiver: ColExpr) -> syn::Result<ColExpr> {
+        let mut res = receiver;
+        while Self::next_is_method(&input) {
+            input.parse::<Token![.]>()?;
+            let method = input.parse::<syn::Ident>()?.into();
+            let content;
+            parenthesized!(content in input);
+            res = ColExpr::Method(ColExprMethod {
+                receiver: Box::new(res),
+                method,
+                args: Punctuated::parse_terminated(&content)?,
+            });
+        }
+        Ok(res)
+    }
+}
+
+impl ColumnToSql for ColExprMethod {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        if ctx.context.is_empty() {
+            // same as call: a column at the beginning of select needs an alias
+            return Err(SqloError::new_spanned(
+                self,
+                "Call must be followed by `as` with an identifier",
+            ));
+        }
+        ctx.context.push(Context::Call);
+        let res = self.method_to_sql(ctx);
+        ctx.context.pop();
+        res
+    }
+}
+
+impl ColExprMethod {
+    fn method_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        let nb_args = match self.method.as_str() {
+            "trim" | "trim_start" | "trim_end" | "to_lowercase" | "to_uppercase" | "len"
+            | "abs" => 0..=0,
+            "round" => 0..=1,
+            "unwrap_or" | "starts_with" | "ends_with" | "contains" => 1..=1,
+            _ => {
+                return Err(SqloError::new_spanned(
+                    &self.method,
+                    format!(
+                        "Unsupported method `{}`, supported methods are: {SUPPORTED_METHODS}",
+                        self.method
+                    ),
+                ))
+            }
+        };
+        if !nb_args.contains(&self.args.len()) {
+            return Err(SqloError::new_spanned(
+                self,
+                format!(
+                    "`{}` expects {} argument(s)",
+                    self.method,
+                    nb_args.map(|n| n.to_string()).join(" or ")
+                ),
+            ));
+        }
+        let receiver = self.receiver.column_to_sql(ctx)?;
+        let function = match self.method.as_str() {
+            "trim" => "TRIM",
+            "trim_start" => "LTRIM",
+            "trim_end" => "RTRIM",
+            "to_lowercase" => "LOWER",
+            "to_uppercase" => "UPPER",
+            "abs" => "ABS",
+            "round" => "ROUND",
+            "unwrap_or" => "COALESCE",
+            "len" => {
+                let query = length(&receiver.query);
+                return Ok(Fragment { query, ..receiver });
+            }
+            like => return self.like_to_sql(like, receiver, ctx),
+        };
+        let mut res = receiver;
+        for arg in &self.args {
+            res = res + arg.column_to_sql(ctx)?;
+        }
+        res.prepend_str(&format!("{function}("));
+        res.append_str(")");
+        Ok(res)
+    }
+
+    // starts_with, ends_with and contains are a case sensitive match of the escaped argument
+    // with wildcards added.
+    fn like_to_sql(
+        &self,
+        method: &str,
+        receiver: Fragment,
+        ctx: &mut Generator,
+    ) -> Result<Fragment, SqloError> {
+        let (before, after) = match method {
+            "starts_with" => ("", PATTERN_WILDCARD),
+            "ends_with" => (PATTERN_WILDCARD, ""),
+            _ => (PATTERN_WILDCARD, PATTERN_WILDCARD),
+        };
+        let pattern = match &self.args[0] {
+            // pattern of a literal is built here
+            ColExpr::Literal(Lit::Str(s)) => {
+                let value = escape_pattern(&s.value());
+                let lit = LitStr::new(&format!("{before}{value}{after}"), s.span());
+                Lit::Str(lit).column_to_sql(ctx)?
+            }
+            arg => {
+                let mut res = arg.column_to_sql(ctx)?;
+                res.query = PATTERN_ESCAPES.iter().fold(res.query, |query, (from, to)| {
+                    format!("REPLACE({query}, '{from}', '{to}')")
+                });
+                if !before.is_empty() {
+                    res.query = concat(&format!("'{before}'"), &res.query);
+                }
+                if !after.is_empty() {
+                    res.query = concat(&res.query, &format!("'{after}'"));
+                }
+                res
+            }
+        };
+        let query = pattern_match(&receiver.query, &pattern.query);
+        let mut res = receiver.add_no_comma(pattern);
+        res.query = query;
+        Ok(res)
+    }
+}
+
+// wildcards of the argument are matched as themselves, like with the rust methods
+fn escape_pattern(value: &str) -> String {
+    PATTERN_ESCAPES
+        .iter()
+        .fold(value.to_string(), |value, (from, to)| {
+            value.replace(from, to)
+        })
+}
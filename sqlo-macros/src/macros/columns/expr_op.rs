@@ -0,0 +1,248 @@
+use darling::util::IdentString;
+use quote::{format_ident, ToTokens};
+use syn::Expr;
+
+use crate::{
+    database::{in_list, quantified_list, quantified_subquery, rewrite_operation, LIST_AS_JSON},
+    error::SqloError,
+    macros::{ColumnToSql, Context, Fragment, Generator, Operator},
+};
+
+use super::{ColExpr, ColExprSubSelect};
+
+#[derive(Debug, Clone)]
+pub struct ColExprOp {
+    pub lhs: Box<ColExpr>,
+    pub op: Operator,
+    pub rhs: Box<ColExpr>,
+}
+
+impl quote::ToTokens for ColExprOp {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.lhs.to_tokens(tokens);
+        self.op.to_tokens(tokens);
+        self.rhs.to_tokens(tokens);
+    }
+}
+
+impl ColumnToSql for ColExprOp {
+    fn column_to_sql(
+        &self,
+        ctx: &mut Generator,
+    ) -> Result<crate::macros::Fragment, crate::error::SqloError> {
+        ctx.context.push(Context::Operation);
+        let lhs = self.lhs.column_to_sql(ctx)?;
+        if let ColExpr::Ident(i) = self.rhs.as_ref() {
+            if i.as_str() == "None" {
+                match &self.op {
+                    Operator::Eq => {
+                        return Ok(self.lhs.column_to_sql(ctx)?.add_no_comma(" IS NULL".into()))
+                    }
+
+                    Operator::Neq => {
+                        return Ok(self
+                            .lhs
+                            .column_to_sql(ctx)?
+                            .add_no_comma(" IS NOT NULL".into()))
+                    }
+                    _ => {
+                        return Err(SqloError::new_spanned(
+                            self.op,
+                            "None must be used with == or !=",
+                        ))
+                    }
+                }
+            }
+        };
+        if let Some((all, quantified)) = quantified(&self.rhs, ctx)? {
+            ctx.context.pop();
+            return quantified_to_sql(lhs, self, all, quantified, ctx);
+        }
+        let negated = matches!(self.op, Operator::NotIn);
+        if let ColExpr::Range(range) = self.rhs.as_ref() {
+            if !matches!(self.op, Operator::In | Operator::NotIn) {
+                return Err(SqloError::new_spanned(
+                    self.op,
+                    "range must be used with `in` or `!in`",
+                ));
+            }
+            ctx.context.pop();
+            let mut res = range.condition_to_sql(lhs, ctx)?;
+            if negated {
+                res.prepend_str("NOT ");
+            }
+            return Ok(res);
+        }
+        if let (Operator::In | Operator::NotIn, Some(list)) =
+            (self.op, runtime_list(&self.rhs, ctx)?)
+        {
+            ctx.context.pop();
+            return list_to_sql(lhs, list, negated, ctx);
+        }
+        let sign = self.op.column_to_sql(ctx)?;
+        if let ColExpr::Optional(expr) = self.rhs.as_ref() {
+            ctx.context.pop();
+            return optional_to_sql(lhs, sign, expr, ctx);
+        }
+        let rhs = self.rhs.column_to_sql(ctx)?;
+        ctx.context.pop();
+        let query = operation_query(&lhs.query, &sign.query, &rhs.query);
+        let mut res = lhs.add_no_comma(sign).add_no_comma(rhs);
+        res.query = query;
+        Ok(res)
+    }
+}
+
+fn operation_query(lhs: &str, sign: &str, rhs: &str) -> String {
+    rewrite_operation(lhs, sign, rhs).unwrap_or_else(|| format!("{lhs} {sign} {rhs}"))
+}
+
+// `lhs op ?::value` is always true when value is None: `($1 IS NULL OR lhs op $1)`.
+fn optional_to_sql(
+    lhs: Fragment,
+    sign: Fragment,
+    expr: &Expr,
+    ctx: &mut Generator,
+) -> Result<Fragment, SqloError> {
+    if !ctx.context.contains(&Context::Where) {
+        return Err(SqloError::new_spanned(
+            expr,
+            "`?::` is only supported in where clause",
+        ));
+    }
+    let value = expr.column_to_sql(ctx)?;
+    let query = format!(
+        "({} IS NULL OR {})",
+        value.query,
+        operation_query(&lhs.query, &sign.query, &value.query)
+    );
+    let mut res = lhs.add_no_comma(value);
+    res.query = query;
+    Ok(res)
+}
+
+// `in ::ids` or `in ids` when `ids` isn't a column: a list known at runtime.
+fn runtime_list(rhs: &ColExpr, ctx: &Generator) -> Result<Option<Expr>, SqloError> {
+    match rhs {
+        ColExpr::Value(expr) => Ok(Some(expr.clone())),
+        ColExpr::Ident(ident)
+            if ctx.main_sqlo.field(ident.as_ident()).is_none()
+                && !ctx.aliases.contains_key(ident) =>
+        {
+            Ok(Some(syn::parse2(ident.to_token_stream())?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn list_to_sql(
+    lhs: Fragment,
+    list: Expr,
+    negated: bool,
+    ctx: &mut Generator,
+) -> Result<Fragment, SqloError> {
+    let value = list_value(list, ctx)?;
+    let mut query = in_list(&lhs.query, &value.query);
+    if negated {
+        query = format!("NOT {query}");
+    }
+    let mut res = lhs.add_no_comma(value);
+    res.query = query;
+    Ok(res)
+}
+
+// Json lists are converted to an owned String before the query, see `expander::list_bindings`.
+fn list_value(list: Expr, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+    let list = if LIST_AS_JSON {
+        if ctx.context.contains(&Context::SubQuery) {
+            return Err(SqloError::new_spanned(
+                list,
+                "runtime list is not supported in subquery with this database",
+            ));
+        }
+        let binding = format_ident!("__sqlo_list_{}", ctx.runtime_lists.len());
+        ctx.runtime_lists.push((binding.clone(), list));
+        syn::parse_quote!(#binding)
+    } else {
+        list
+    };
+    list.column_to_sql(ctx)
+}
+
+enum Quantified<'a> {
+    SubQuery(&'a ColExprSubSelect),
+    List(Box<Expr>),
+}
+
+// `op any {...}`, `op all {...}`, `op any(::list)` or `op all(::list)`
+fn quantified<'a>(
+    rhs: &'a ColExpr,
+    ctx: &Generator,
+) -> Result<Option<(bool, Quantified<'a>)>, SqloError> {
+    let is_all = |func: &IdentString| match func.as_str().to_lowercase().as_str() {
+        "any" => Some(false),
+        "all" => Some(true),
+        _ => None,
+    };
+    match rhs {
+        ColExpr::SubSelect(sub) => Ok(sub
+            .func()
+            .and_then(is_all)
+            .map(|all| (all, Quantified::SubQuery(sub)))),
+        ColExpr::Call(call) => {
+            let all = match is_all(&call.base) {
+                Some(all) => all,
+                None => return Ok(None),
+            };
+            match call.args.single().map(|arg| runtime_list(arg, ctx)) {
+                Some(Ok(Some(list))) => Ok(Some((all, Quantified::List(Box::new(list))))),
+                Some(Err(e)) => Err(e),
+                _ => Err(SqloError::new_spanned(
+                    call,
+                    "any/all expects a subquery or a runtime list",
+                )),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+fn quantified_to_sql(
+    lhs: Fragment,
+    expr_op: &ColExprOp,
+    all: bool,
+    quantified: Quantified,
+    ctx: &mut Generator,
+) -> Result<Fragment, SqloError> {
+    if !matches!(
+        expr_op.op,
+        Operator::Eq | Operator::Neq | Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge
+    ) {
+        return Err(SqloError::new_spanned(
+            expr_op.op,
+            "any/all must be used with a comparison operator",
+        ));
+    }
+    let sign = expr_op.op.column_to_sql(ctx)?;
+    let (value, query) = match quantified {
+        Quantified::SubQuery(sub) => {
+            let value = sub.subquery_to_sql(ctx)?;
+            let query = quantified_subquery(&lhs.query, &sign.query, all, &value.query);
+            (value, query)
+        }
+        Quantified::List(list) => {
+            let value = list_value(*list, ctx)?;
+            let query = quantified_list(&lhs.query, &sign.query, all, &value.query);
+            (value, query)
+        }
+    };
+    let query = query.ok_or_else(|| {
+        SqloError::new_spanned(
+            &expr_op.rhs,
+            "only `== any(list)` and `!= all(list)` are supported with this database",
+        )
+    })?;
+    let mut res = lhs.add_no_comma(value);
+    res.query = query;
+    Ok(res)
+}
@@ -7,6 +7,24 @@ use super::ColExpr;
 #[derive(Debug, Clone)]
 pub struct ColExprParen(Punctuated<ColExpr, Token![,]>);
 
+impl ColExprParen {
+    // arguments of a call, which may be empty: `row_number()`
+    pub fn parse_call_args(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        Ok(Punctuated::parse_terminated(&content)?.into())
+    }
+
+    // the only expression inside the parentheses
+    pub fn single(&self) -> Option<&ColExpr> {
+        if self.0.len() == 1 {
+            self.0.first()
+        } else {
+            None
+        }
+    }
+}
+
 impl ColumnToSql for ColExprParen {
     fn column_to_sql(
         &self,
@@ -0,0 +1,78 @@
+use syn::RangeLimits;
+
+use crate::{
+    error::SqloError,
+    macros::{ColumnToSql, Fragment, Generator},
+};
+
+use super::ColExpr;
+
+#[derive(Debug, Clone)]
+pub struct ColExprRange {
+    pub start: Option<Box<ColExpr>>,
+    pub limits: RangeLimits,
+    pub end: Option<Box<ColExpr>>,
+}
+
+impl quote::ToTokens for ColExprRange {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.start.to_tokens(tokens);
+        self.limits.to_tokens(tokens);
+        self.end.to_tokens(tokens);
+    }
+}
+
+impl ColumnToSql for ColExprRange {
+    fn column_to_sql(&self, _ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        Err(SqloError::new_spanned(
+            self,
+            "range must be used after `in` or as a match arm",
+        ))
+    }
+}
+
+impl ColExprRange {
+    // `column in start..=end` is a BETWEEN, `column in start..end` is half-open.
+    pub fn condition_to_sql(
+        &self,
+        column: Fragment,
+        ctx: &mut Generator,
+    ) -> Result<Fragment, SqloError> {
+        let closed = matches!(self.limits, RangeLimits::Closed(_));
+        let start = self
+            .start
+            .as_ref()
+            .map(|s| s.column_to_sql(ctx))
+            .transpose()?;
+        let end = self
+            .end
+            .as_ref()
+            .map(|e| e.column_to_sql(ctx))
+            .transpose()?;
+        let end_sign = if closed { "<=" } else { "<" };
+        match (start, end) {
+            (Some(start), Some(end)) if closed => {
+                let mut res = column.add_no_comma("BETWEEN".into()).add_no_comma(start);
+                res.append_str(" AND");
+                Ok(res.add_no_comma(end))
+            }
+            (Some(start), Some(end)) => {
+                let mut res = column.clone().add_no_comma(">=".into()).add_no_comma(start);
+                res.prepend_str("(");
+                res.append_str(" AND");
+                let mut res = res
+                    .add_no_comma(column)
+                    .add_no_comma(end_sign.into())
+                    .add_no_comma(end);
+                res.append_str(")");
+                Ok(res)
+            }
+            (Some(start), None) => Ok(column.add_no_comma(">=".into()).add_no_comma(start)),
+            (None, Some(end)) => Ok(column.add_no_comma(end_sign.into()).add_no_comma(end)),
+            (None, None) => Err(SqloError::new_spanned(
+                self,
+                "range needs at least one bound",
+            )),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     error::SqloError,
-    macros::{ColumnToSql, Context, Fragment, Generator, Mode, SelectParser},
+    macros::{scoped_sqlos, ColumnToSql, Context, Fragment, Generator, Mode, SelectParser},
 };
 use darling::util::IdentString;
 use proc_macro2::TokenStream;
@@ -19,27 +19,40 @@ impl quote::ToTokens for ColExprSubSelect {
     }
 }
 
-impl ColumnToSql for ColExprSubSelect {
-    fn column_to_sql(
-        &self,
-        ctx: &mut Generator,
-    ) -> Result<crate::macros::Fragment, crate::error::SqloError> {
+impl ColExprSubSelect {
+    pub fn func(&self) -> Option<&IdentString> {
+        self.func.as_ref()
+    }
+
+    // the parenthesized subquery without its function prefix
+    pub fn subquery_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
         ctx.context.push(Context::SubQuery);
         let parsed = syn::parse2::<SelectParser>(self.tokens.clone()).map_err(SqloError::from)?;
+        let sqlos = scoped_sqlos(&parsed, ctx.sqlos)?;
         let result = Generator::from_sqlo_query_parse(
             Mode::Select,
             parsed,
-            ctx.sqlos,
+            &sqlos,
             true,
-            ctx.tables.clone(),
+            ctx.tables.with_sqlos(&sqlos),
         )?;
         let mut qr: Fragment = result.try_into()?;
         qr.prepend_str("(");
+        qr.append_str(")");
+        ctx.context.pop();
+        Ok(qr)
+    }
+}
+
+impl ColumnToSql for ColExprSubSelect {
+    fn column_to_sql(
+        &self,
+        ctx: &mut Generator,
+    ) -> Result<crate::macros::Fragment, crate::error::SqloError> {
+        let mut qr = self.subquery_to_sql(ctx)?;
         if let Some(func) = &self.func {
             qr.prepend_str(func.as_str())
         }
-        qr.append_str(")");
-        ctx.context.pop();
         Ok(qr)
     }
 }
@@ -0,0 +1,74 @@
+use syn::{parenthesized, Token};
+
+use crate::{
+    error::SqloError,
+    macros::{kw, ColumnToSql, Context, Fragment, Generator, OrderBy},
+};
+
+use super::ColExpr;
+
+/// Window of a call: `over(partition_by a, b order_by -c)`
+#[derive(Debug, Clone)]
+pub struct Window {
+    partition_by: Vec<ColExpr>,
+    order_by: Option<OrderBy>,
+}
+
+impl quote::ToTokens for Window {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let partition_by = &self.partition_by;
+        let order_by = &self.order_by;
+        quote::quote! {over(partition_by #(#partition_by),* #order_by)}.to_tokens(tokens);
+    }
+}
+
+impl Window {
+    pub fn next_is_window(input: &syn::parse::ParseStream) -> bool {
+        input.peek(kw::over) && input.peek2(syn::token::Paren)
+    }
+}
+
+impl syn::parse::Parse for Window {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::over>()?;
+        let content;
+        parenthesized!(content in input);
+        let mut partition_by = vec![];
+        if content.peek(kw::partition_by) {
+            content.parse::<kw::partition_by>()?;
+            partition_by.push(content.parse::<ColExpr>()?);
+            while content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+                partition_by.push(content.parse::<ColExpr>()?);
+            }
+        }
+        let order_by = if content.peek(kw::order_by) {
+            Some(content.parse::<OrderBy>()?)
+        } else {
+            None
+        };
+        if !content.is_empty() {
+            return Err(content.error("expected `partition_by` or `order_by`"));
+        }
+        Ok(Window {
+            partition_by,
+            order_by,
+        })
+    }
+}
+
+impl ColumnToSql for Window {
+    fn column_to_sql(&self, ctx: &mut Generator) -> Result<Fragment, SqloError> {
+        ctx.context.push(Context::Call);
+        let mut res = Fragment::from_iterator(&self.partition_by, ctx)?;
+        if !self.partition_by.is_empty() {
+            res.prepend_str("PARTITION BY ");
+        }
+        if let Some(order_by) = &self.order_by {
+            res = res.add_no_comma(order_by.column_to_sql(ctx)?);
+        }
+        ctx.context.pop();
+        res.query = format!("OVER ({})", res.query.trim());
+        Ok(res)
+    }
+}
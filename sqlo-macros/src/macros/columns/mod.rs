@@ -5,10 +5,13 @@ mod expr_call;
 mod expr_case;
 mod expr_cast;
 mod expr_field;
+mod expr_method;
 mod expr_op;
 mod expr_paren;
+mod expr_range;
 mod expr_subselect;
 mod expr_unary;
+mod expr_window;
 
 pub use assignment::{Assign, Assigns};
 pub use col_expr::ColExpr;
@@ -17,7 +20,10 @@ pub use expr_call::ColExprCall;
 pub use expr_case::ColExprCase;
 pub use expr_cast::{AliasCast, ColumnCast};
 pub use expr_field::ColExprField;
+pub use expr_method::ColExprMethod;
 pub use expr_op::ColExprOp;
 pub use expr_paren::ColExprParen;
+pub use expr_range::ColExprRange;
 pub use expr_subselect::ColExprSubSelect;
 pub use expr_unary::{unarize, ColExprUnary};
+pub use expr_window::Window;
@@ -0,0 +1,150 @@
+use darling::util::IdentString;
+
+use super::{
+    parse_bracketed, parse_dbg_symbol, parse_optional_field_member, parse_optional_where,
+    parse_parenthezide, parse_sqlo_struct_ident, Assigns, Clauses, Column, Fetch, PkValue,
+    QueryParser,
+};
+
+pub struct DeleteParser {
+    #[cfg(debug_assertions)]
+    debug: bool,
+    entity: IdentString,
+    pk_value: PkValue,
+    related: Option<IdentString>,
+    clauses: Clauses,
+    fetch: Fetch,
+}
+
+impl QueryParser for DeleteParser {
+    fn debug(&self) -> bool {
+        self.debug
+    }
+
+    fn entity(&self) -> &IdentString {
+        &self.entity
+    }
+
+    fn related(&self) -> &Option<IdentString> {
+        &self.related
+    }
+
+    fn assigns(&self) -> &Assigns {
+        unreachable!("No assignment with delete statment")
+    }
+
+    fn include(&self) -> Option<&IdentString> {
+        None
+    }
+
+    fn recursive(&self) -> Option<&super::Recursive> {
+        None
+    }
+
+    fn compound(&self) -> Option<&super::Compound> {
+        None
+    }
+
+    fn derived(&self) -> Option<&super::Cte> {
+        None
+    }
+
+    fn rows(&self) -> &[Assigns] {
+        unreachable!("No rows with delete statment")
+    }
+
+    fn custom_struct(&self) -> Option<IdentString> {
+        None
+    }
+
+    fn pk_value(&self) -> PkValue {
+        self.pk_value.clone()
+    }
+
+    fn clauses(&self) -> &Clauses {
+        &self.clauses
+    }
+
+    fn columns(&self) -> &[Column] {
+        unreachable!("Must not be used with delete statment")
+    }
+
+    fn fetch(&self) -> Fetch {
+        self.fetch
+    }
+}
+
+impl syn::parse::Parse for DeleteParser {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        #[cfg(debug_assertions)]
+        let debug = input.call(parse_dbg_symbol)?;
+
+        // parse fetch mode
+        let fetch: Fetch = input.parse()?;
+
+        // parse sqlo ident
+        let entity = input.call(parse_sqlo_struct_ident)?;
+        // or ident[pk] or  ident[pk].related or ident(instance) or ...
+        let mut pk_value = input.call(parse_bracketed)?;
+        if let PkValue::None = pk_value {
+            pk_value = input.call(parse_parenthezide)?;
+        }
+        let related = input.call(parse_optional_field_member)?;
+
+        // where clause
+        let mut clauses = Clauses::new();
+        clauses.try_push(input, parse_optional_where)?;
+
+        Ok(DeleteParser {
+            debug,
+            entity,
+            related,
+            pk_value,
+            clauses,
+            fetch,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_sqlo_delete_macro {
+    use super::*;
+
+    macro_rules! success_parse_sqlo_delete_syntax {
+        ($case:ident, $input:literal) => {
+            paste::paste! {
+
+                #[test]
+                fn [<test_parse_delete_syntax_ success_ $case>]() {
+                    syn::parse_str::<DeleteParser>($input).unwrap();
+                }
+            }
+        };
+    }
+
+    success_parse_sqlo_delete_syntax!(all_via_struct_ident, "Maison");
+    success_parse_sqlo_delete_syntax!(pk, "Maison[1]");
+    success_parse_sqlo_delete_syntax!(instance, "Maison(maison)");
+    success_parse_sqlo_delete_syntax!(returning, ". Maison[1]");
+    success_parse_sqlo_delete_syntax!(where_clause, "Maison where 1 == 1");
+    success_parse_sqlo_delete_syntax!(related_where, "Maison[1].related where bed == false");
+
+    macro_rules! fail_parse_sqlo_delete_syntax {
+        ($case:ident, $input:literal, $err:literal) => {
+            paste::paste! {
+
+                #[test]
+                fn [<test_parse_delete_syntax_ fail $case>]() {
+                    assert_eq!(syn::parse_str::<DeleteParser>($input).err().unwrap().to_string(),$err.to_string())
+                }
+            }
+        };
+    }
+
+    fail_parse_sqlo_delete_syntax!(
+        empty,
+        "dbg!",
+        "unexpected end of input, Derived Sqlo struct expected"
+    );
+    fail_parse_sqlo_delete_syntax!(no_column, "Maison id", "unexpected token");
+}
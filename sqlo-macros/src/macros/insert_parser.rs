@@ -0,0 +1,203 @@
+use darling::util::IdentString;
+use syn::{punctuated::Punctuated, Token};
+
+use crate::database::SUPPORTS_RETURNING;
+
+use super::{
+    parse_dbg_symbol, parse_optional_on_conflict, parse_parenthezide, parse_sqlo_struct_ident,
+    Assigns, Clauses, Column, Fetch, PkValue, QueryParser,
+};
+
+pub struct InsertParser {
+    #[cfg(debug_assertions)]
+    debug: bool,
+    entity: IdentString,
+    rows: Vec<Assigns>,
+    instances: PkValue,
+    clauses: Clauses,
+    fetch: Fetch,
+}
+
+impl QueryParser for InsertParser {
+    fn debug(&self) -> bool {
+        self.debug
+    }
+
+    fn entity(&self) -> &IdentString {
+        &self.entity
+    }
+
+    fn related(&self) -> &Option<IdentString> {
+        &None
+    }
+
+    fn assigns(&self) -> &Assigns {
+        unreachable!("Insert statment uses rows")
+    }
+
+    fn include(&self) -> Option<&IdentString> {
+        None
+    }
+
+    fn recursive(&self) -> Option<&super::Recursive> {
+        None
+    }
+
+    fn compound(&self) -> Option<&super::Compound> {
+        None
+    }
+
+    fn derived(&self) -> Option<&super::Cte> {
+        None
+    }
+
+    fn rows(&self) -> &[Assigns] {
+        &self.rows
+    }
+
+    fn custom_struct(&self) -> Option<IdentString> {
+        None
+    }
+
+    fn pk_value(&self) -> PkValue {
+        self.instances.clone()
+    }
+
+    fn clauses(&self) -> &Clauses {
+        &self.clauses
+    }
+
+    fn columns(&self) -> &[Column] {
+        unreachable!("Must not be used with insert statment")
+    }
+
+    fn fetch(&self) -> Fetch {
+        self.fetch
+    }
+}
+
+// parse `[(a=1, b=2), (a=3, b=4)]`
+fn parse_rows(input: syn::parse::ParseStream) -> syn::Result<Vec<Assigns>> {
+    let content;
+    syn::bracketed!(content in input);
+    let rows = Punctuated::<Assigns, Token![,]>::parse_separated_nonempty_with(&content, |row| {
+        let content;
+        syn::parenthesized!(content in row);
+        content.parse::<Assigns>()
+    })?;
+    Ok(rows.into_iter().collect())
+}
+
+impl syn::parse::Parse for InsertParser {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        #[cfg(debug_assertions)]
+        let debug = input.call(parse_dbg_symbol)?;
+
+        // parse fetch mode
+        let fetch: Fetch = input.parse()?;
+
+        // parse sqlo ident
+        let entity = input.call(parse_sqlo_struct_ident)?;
+
+        // ident(instances) or ident; [(..), (..)] or ident a=1, b=2
+        let instances = input.call(parse_parenthezide)?;
+        let rows = if let PkValue::Parenthezide(_) = instances {
+            if !matches!(fetch, Fetch::None | Fetch::All) {
+                return Err(syn::Error::new(
+                    entity.span(),
+                    "Only `*` or no fetch mode are supported when inserting instances",
+                ));
+            }
+            if matches!(fetch, Fetch::All) && !SUPPORTS_RETURNING {
+                return Err(syn::Error::new(
+                    entity.span(),
+                    "Inserted instances can't be returned: no `RETURNING` with this database",
+                ));
+            }
+            vec![]
+        } else if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+            input.call(parse_rows)?
+        } else {
+            vec![Assigns::parse(input)?]
+        };
+
+        // upsert
+        let mut clauses = Clauses::new();
+        clauses.try_push(input, parse_optional_on_conflict)?;
+
+        Ok(InsertParser {
+            debug,
+            entity,
+            rows,
+            instances,
+            clauses,
+            fetch,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_sqlo_insert_macro {
+    use super::*;
+
+    macro_rules! success_parse_sqlo_insert_syntax {
+        ($case:ident, $input:literal) => {
+            paste::paste! {
+
+                #[test]
+                fn [<test_parse_insert_syntax_ success_ $case>]() {
+                    syn::parse_str::<InsertParser>($input).unwrap();
+                }
+            }
+        };
+    }
+
+    success_parse_sqlo_insert_syntax!(one_row, "Maison id=1, taille=2");
+    success_parse_sqlo_insert_syntax!(many_rows, "Maison; [(id=1, taille=2), (id=3, taille=4)]");
+    success_parse_sqlo_insert_syntax!(many_rows_returning, "* Maison; [(id=1), (id=3)]");
+    success_parse_sqlo_insert_syntax!(instances, "Maison(&maisons)");
+    #[cfg(not(feature = "mysql"))]
+    success_parse_sqlo_insert_syntax!(instances_returning, "* Maison(maisons)");
+    success_parse_sqlo_insert_syntax!(on_conflict_nothing, "Maison id=1 on_conflict(id) nothing");
+    success_parse_sqlo_insert_syntax!(
+        on_conflict_update,
+        "Maison id=1, taille=2 on_conflict(id) update taille=excluded.taille, adresse=\"a\""
+    );
+    success_parse_sqlo_insert_syntax!(
+        on_conflict_many_rows,
+        "Maison; [(id=1), (id=2)] on_conflict(id, adresse) nothing"
+    );
+    success_parse_sqlo_insert_syntax!(
+        on_conflict_instances,
+        "Maison(&maisons) on_conflict(id) nothing"
+    );
+
+    macro_rules! fail_parse_sqlo_insert_syntax {
+        ($case:ident, $input:literal, $err:literal) => {
+            paste::paste! {
+
+                #[test]
+                fn [<test_parse_insert_syntax_ fail $case>]() {
+                    assert_eq!(syn::parse_str::<InsertParser>($input).err().unwrap().to_string(),$err.to_string())
+                }
+            }
+        };
+    }
+
+    fail_parse_sqlo_insert_syntax!(
+        empty_rows,
+        "Maison; []",
+        "unexpected end of input, expected parentheses"
+    );
+    fail_parse_sqlo_insert_syntax!(
+        on_conflict_no_action,
+        "Maison id=1 on_conflict(id)",
+        "unexpected end of input, expect `update` or `nothing` after `on_conflict(...)`"
+    );
+    fail_parse_sqlo_insert_syntax!(
+        instances_fetch_one,
+        ". Maison(maisons)",
+        "Only `*` or no fetch mode are supported when inserting instances"
+    );
+}
@@ -1,6 +1,7 @@
 mod builder;
 mod clauses;
 mod columns;
+mod delete_parser;
 mod insert_parser;
 mod select_parser;
 mod update_parser;
@@ -8,6 +9,7 @@ mod update_parser;
 pub use builder::*;
 pub use clauses::*;
 pub use columns::*;
+pub use delete_parser::DeleteParser;
 pub use insert_parser::InsertParser;
 pub use select_parser::SelectParser;
 pub use update_parser::UpdateParser;
@@ -0,0 +1,332 @@
+use darling::util::IdentString;
+
+#[cfg(debug_assertions)]
+use super::parse_dbg_symbol;
+use super::{
+    kw, parse_bracketed, parse_optional_field_member, parse_optional_include, Fetch, PkValue,
+    QueryParser, Recursive, SetOperator,
+};
+
+use crate::macros::{
+    parse_optional_columns, parse_optional_group_by, parse_optional_having,
+    parse_optional_ident_with_comma, parse_optional_limit_page, parse_optional_order_by,
+    parse_optional_where, parse_optional_with, parse_sqlo_struct_ident,
+};
+
+use crate::macros::{Clause, Clauses, Column, Compound, Cte};
+
+#[derive(Debug, Clone)]
+pub struct SelectParser {
+    #[cfg(debug_assertions)]
+    debug: bool,
+    entity: IdentString,
+    related: Option<IdentString>,
+    include: Option<IdentString>,
+    recursive: Option<Recursive>,
+    compound: Option<Compound>,
+    derived: Option<Box<Cte>>,
+    customs: Vec<Column>,
+    custom_struct: Option<IdentString>,
+    pk_value: PkValue,
+    clauses: Clauses,
+    fetch: Fetch,
+}
+
+impl syn::parse::Parse for SelectParser {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        #[cfg(debug_assertions)]
+        let debug = input.call(parse_dbg_symbol)?;
+
+        // common table expressions come first, as in sql
+        let mut clauses = Clauses::new();
+        clauses.try_push(input, parse_optional_with)?;
+
+        // parse fetch type
+        let fetch = input.parse()?;
+
+        // First: parse cust struct
+        let custom_struct = input.call(parse_optional_ident_with_comma)?;
+
+        // then parse sqlo ident, or a subquery named as a pseudo sqlo
+        let derived = if input.peek(syn::token::Brace) {
+            Some(Box::new(input.call(Cte::parse_derived)?))
+        } else {
+            None
+        };
+        let entity = match &derived {
+            Some(derived) => derived.name().clone(),
+            None => input.call(parse_sqlo_struct_ident)?,
+        };
+        // or  ident[pk].related
+        let pk_value = input.call(parse_bracketed)?;
+        let related = input.call(parse_optional_field_member)?;
+        // tree traversal of a self join
+        let recursive = if related.is_some() && input.peek(kw::recursive) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        // eager loading of related rows
+        let include = input.call(parse_optional_include)?;
+
+        // parse optional custom colums
+        let customs = input
+            .call(parse_optional_columns)?
+            .map(|x| x.into_iter().collect())
+            .unwrap_or_default();
+
+        // rest of the clauses
+        clauses.try_push(input, parse_optional_where)?;
+        clauses.try_push(input, parse_optional_group_by)?;
+        clauses.try_push(input, parse_optional_having)?;
+        clauses.try_push(input, parse_optional_order_by)?;
+        clauses.try_push(input, parse_optional_limit_page)?;
+
+        // combined with another query
+        let compound = if SetOperator::next_is_set_operator(&input) {
+            if clauses
+                .iter()
+                .any(|c| matches!(c, Clause::OrderBy(_) | Clause::Limit(_)))
+            {
+                return Err(input.error(
+                    "`order_by` and `limit` must follow the last query of a combined query",
+                ));
+            }
+            let operator = input.parse()?;
+            let span = input.span();
+            let mut query = input.parse::<SelectParser>()?;
+            if !matches!(query.fetch, Fetch::None)
+                || query.custom_struct.is_some()
+                || query.recursive.is_some()
+                || query.clauses.iter().any(|c| matches!(c, Clause::With(_)))
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "fetch, custom struct, `with` and `recursive` are only supported by the first query",
+                ));
+            }
+            // order_by and limit of the last query apply to the combined query
+            for clause in query.clauses.take_ordering() {
+                clauses.push(clause);
+            }
+            Some(Compound {
+                operator,
+                query: Box::new(query),
+            })
+        } else {
+            None
+        };
+
+        Ok(SelectParser {
+            debug,
+            entity,
+            related,
+            include,
+            recursive,
+            compound,
+            derived,
+            customs,
+            custom_struct,
+            pk_value,
+            clauses,
+            fetch,
+        })
+    }
+}
+
+impl QueryParser for SelectParser {
+    fn debug(&self) -> bool {
+        self.debug
+    }
+
+    fn entity(&self) -> &IdentString {
+        &self.entity
+    }
+
+    fn related(&self) -> &Option<IdentString> {
+        &self.related
+    }
+
+    fn include(&self) -> Option<&IdentString> {
+        self.include.as_ref()
+    }
+
+    fn recursive(&self) -> Option<&Recursive> {
+        self.recursive.as_ref()
+    }
+
+    fn compound(&self) -> Option<&Compound> {
+        self.compound.as_ref()
+    }
+
+    fn derived(&self) -> Option<&Cte> {
+        self.derived.as_deref()
+    }
+
+    fn columns(&self) -> &[Column] {
+        &self.customs
+    }
+
+    fn custom_struct(&self) -> Option<IdentString> {
+        self.custom_struct.clone()
+    }
+
+    fn pk_value(&self) -> PkValue {
+        self.pk_value.clone()
+    }
+
+    fn clauses(&self) -> &Clauses {
+        &self.clauses
+    }
+
+    fn assigns(&self) -> &super::Assigns {
+        panic!("Assign must not be used with Select")
+    }
+
+    fn rows(&self) -> &[super::Assigns] {
+        panic!("Rows must not be used with Select")
+    }
+
+    fn fetch(&self) -> Fetch {
+        self.fetch
+    }
+}
+
+#[cfg(test)]
+mod test_sqlo_select_macro {
+    use super::*;
+
+    macro_rules! success_parse_sqlo_select_syntax {
+        ($case:ident, $input:literal) => {
+            paste::paste! {
+
+                #[test]
+                fn [<test_parse_select_syntax_ success_ $case>]() {
+                    syn::parse_str::<SelectParser>($input).unwrap();
+                }
+            }
+        };
+    }
+
+    success_parse_sqlo_select_syntax!(all_via_struct_ident, "Maison");
+    success_parse_sqlo_select_syntax!(unique_where, "Maison where 1 == 1");
+    success_parse_sqlo_select_syntax!(some_where, "Maison where 1 == 1 || 2 == 2");
+    success_parse_sqlo_select_syntax!(
+        where_with_parenthese,
+        "Maison where  1 == 1 && (33 ==3 || 2 == 2)"
+    );
+    success_parse_sqlo_select_syntax!(order_by, r#"Maison where  1 == 1 && 2 == 2 order_by bla"#);
+    success_parse_sqlo_select_syntax!(
+        order_by_many,
+        r#"Maison where  1 == 1 && 2 == 2 order_by bla,bli"#
+    );
+    success_parse_sqlo_select_syntax!(order_by_column_enum, r#"Maison order_by -::col, id"#);
+    success_parse_sqlo_select_syntax!(
+        where_optional,
+        "Maison where taille > ?::min && adresse # ?::a || id == 1"
+    );
+    success_parse_sqlo_select_syntax!(
+        where_range,
+        "Maison where taille in 1..=5 && id in ..3 || id in ::a.. order_by id"
+    );
+    success_parse_sqlo_select_syntax!(
+        case_range,
+        r#"Maison id, match taille 0..10 => "small", _ => "big" as a"#
+    );
+    success_parse_sqlo_select_syntax!(
+        where_negated_operators,
+        r#"Maison where id !in (1,2) && adresse !# "a%" && adresse #* "A%" || adresse !#* "b""#
+    );
+    success_parse_sqlo_select_syntax!(
+        where_null_safe_and_regex,
+        r#"Maison where piscine <=> ::a && piscine !<=> ::b && adresse ~ "^a" && adresse !~ "b$""#
+    );
+    success_parse_sqlo_select_syntax!(
+        method_chain,
+        r#"Maison adresse.trim().to_lowercase() as a where therooms.name.starts_with("a") && ::b.len() > 1"#
+    );
+    success_parse_sqlo_select_syntax!(
+        window_function,
+        "Maison row_number() over(partition_by adresse, taille order_by -id) as r, sum(taille) over(order_by id) as s, count(*) over() as c"
+    );
+    success_parse_sqlo_select_syntax!(
+        with_ctes,
+        "with a = {Maison id, taille as t where taille > 1}, b = {a where t < 3} *b id, t"
+    );
+    success_parse_sqlo_select_syntax!(ident_related, "Maison[1].related");
+    success_parse_sqlo_select_syntax!(
+        compound,
+        "Maison id where id > 1 union all Maison id intersect Maison id except Maison id order_by -id limit 2"
+    );
+    success_parse_sqlo_select_syntax!(compound_whole_rows, "Maison union Maison");
+    success_parse_sqlo_select_syntax!(
+        derived_table,
+        "{Maison id, count(lespieces.id) as n group_by id} as t avg(t.n) as a where n > 1"
+    );
+    success_parse_sqlo_select_syntax!(
+        recursive,
+        "Maison[1].related recursive depth 3 id, sqlo_depth where sqlo_depth > 1"
+    );
+    success_parse_sqlo_select_syntax!(
+        recursive_field_named_depth,
+        "Maison[1].related recursive depth 3 depth"
+    );
+
+    success_parse_sqlo_select_syntax!(unique_call, "Maison count(id) as bla");
+    success_parse_sqlo_select_syntax!(unique_col_identifier, "Maison id");
+    success_parse_sqlo_select_syntax!(call_plus_col, "Maison id, count(id) as bla");
+    success_parse_sqlo_select_syntax!(include, "Maison include therooms where id > 1");
+    success_parse_sqlo_select_syntax!(column_named_include, "Maison include");
+    success_parse_sqlo_select_syntax!(
+        multi_hop_field,
+        "Maison therooms.beds.color where therooms=.beds.color == 1 order_by therooms.beds=.id"
+    );
+
+    macro_rules! fail_parse_sqlo_select_syntax {
+        ($case:ident, $input:literal, $err:literal) => {
+            paste::paste! {
+
+                #[test]
+                fn [<test_parse_select_syntax_ fail $case>]() {
+                    assert_eq!(syn::parse_str::<SelectParser>($input).err().unwrap().to_string(),$err.to_string())
+                }
+            }
+        };
+    }
+
+    fail_parse_sqlo_select_syntax!(
+        compound_ordered_before,
+        "Maison order_by id union Maison",
+        "`order_by` and `limit` must follow the last query of a combined query"
+    );
+    fail_parse_sqlo_select_syntax!(
+        compound_fetch,
+        "Maison union * Maison",
+        "fetch, custom struct, `with` and `recursive` are only supported by the first query"
+    );
+    fail_parse_sqlo_select_syntax!(
+        recursive_without_depth,
+        "Maison[1].related recursive id",
+        "`recursive` needs a max depth: `recursive depth 10`"
+    );
+    fail_parse_sqlo_select_syntax!(
+        derived_table_without_alias,
+        "{Maison id} count(id) as n",
+        "a subquery in place of a sqlo struct needs an alias: `{...} as name`"
+    );
+    fail_parse_sqlo_select_syntax!(
+        empty,
+        "dbg!",
+        "unexpected end of input, Derived Sqlo struct expected"
+    );
+    fail_parse_sqlo_select_syntax!(
+        not_irder_by_after_binaries,
+        "Maison where 1 == 1 bla",
+        "unexpected token"
+    );
+    fail_parse_sqlo_select_syntax!(
+        not_comma_field_after_order_by,
+        "Maison where 1 == 1 order_by",
+        "unexpected end of input, Sqlo: Invalid input"
+    );
+}
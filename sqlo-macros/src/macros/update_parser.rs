@@ -34,6 +34,26 @@ impl QueryParser for UpdateParser {
         &self.assignments
     }
 
+    fn include(&self) -> Option<&IdentString> {
+        None
+    }
+
+    fn recursive(&self) -> Option<&super::Recursive> {
+        None
+    }
+
+    fn compound(&self) -> Option<&super::Compound> {
+        None
+    }
+
+    fn derived(&self) -> Option<&super::Cte> {
+        None
+    }
+
+    fn rows(&self) -> &[Assigns] {
+        unreachable!("No rows with update statment")
+    }
+
     fn custom_struct(&self) -> Option<IdentString> {
         None
     }
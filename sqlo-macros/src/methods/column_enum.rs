@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::sqlo::Sqlo;
+
+/// `{Ident}Column` enum: one variant per field, parsed from field or column name.
+pub fn impl_column_enum(sqlo: &Sqlo) -> TokenStream {
+    let ident = column_enum_ident(sqlo);
+    let variants = sqlo
+        .fields
+        .iter()
+        .map(|f| format_ident!("{}", heck::AsUpperCamelCase(f.ident.as_str()).to_string()))
+        .collect::<Vec<_>>();
+    let field_names = sqlo
+        .fields
+        .iter()
+        .map(|f| f.ident.as_str())
+        .collect::<Vec<_>>();
+    let column_names = sqlo.fields.iter().map(|f| f.column.as_str());
+
+    // field names first, a column name can't shadow another field
+    let mut seen = HashSet::new();
+    let from_str_arms = sqlo
+        .fields
+        .iter()
+        .zip(variants.iter())
+        .map(|(f, v)| (f.ident.as_str(), v))
+        .chain(
+            sqlo.fields
+                .iter()
+                .zip(variants.iter())
+                .map(|(f, v)| (f.column.as_str(), v)),
+        )
+        .filter(|(name, _)| seen.insert(*name))
+        .map(|(name, variant)| quote! {#name => Ok(Self::#variant)});
+
+    let doc = format!(
+        "Columns of [`{}`], usable in `order_by ::column`.",
+        sqlo.ident
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #ident {
+            #(#variants),*
+        }
+
+        impl #ident {
+            /// Every column, in fields order.
+            pub const ALL: &'static [#ident] = &[#(#ident::#variants),*];
+
+            /// Field name.
+            pub fn field(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #field_names),*
+                }
+            }
+
+            /// Column name.
+            pub fn column(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #column_names),*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for #ident {
+            type Err = ::sqlo::Error;
+
+            /// Parse a field name or a column name.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#from_str_arms,)*
+                    _ => Err(::sqlo::Error::UnknownColumn(s.to_string())),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.field())
+            }
+        }
+    }
+}
+
+pub fn column_enum_ident(sqlo: &Sqlo) -> syn::Ident {
+    format_ident!("{}Column", sqlo.ident.as_ident())
+}
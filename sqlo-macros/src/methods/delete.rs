@@ -1,19 +1,17 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
-use crate::database::{db_ident, db_query_result_path, db_sqlx_path, qmarks};
-use crate::{field::Field, sqlo::Sqlo, types::get_function_arg_type};
+use crate::database::{db_ident, db_query_result_path, db_sqlx_path};
+use crate::sqlo::Sqlo;
+
+use super::{pk_arg_type, pk_arg_values, pk_where_query};
 
 pub fn impl_delete(sqlo: &Sqlo) -> TokenStream {
-    let Sqlo { pk_field, .. } = sqlo;
-    let Field {
-        ident: pk_ident,
-        ty: pk_type,
-        ..
-    } = pk_field;
+    let pk_idents = sqlo.pk_fields.iter().map(|f| &f.ident);
     let query = build_sql_query(sqlo);
 
-    let pk_type = get_function_arg_type(pk_type);
+    let pk_type = pk_arg_type(sqlo);
+    let pk_values = pk_arg_values(sqlo, &format_ident!("pk"));
 
     let database_type = db_ident();
     let db_path = db_sqlx_path();
@@ -24,23 +22,18 @@ pub fn impl_delete(sqlo: &Sqlo) -> TokenStream {
         /// Delete database row.
         /// Instance is moved preventing any other use.
         pub async fn remove<E: sqlx::Executor<'c, Database = #db_path>>(self, pool: E) -> sqlx::Result<#sqlx_qr_path> {
-            sqlx::query!(#query, self.#pk_ident ).execute(pool).await
+            sqlx::query!(#query, #(self.#pk_idents),* ).execute(pool).await
         }
 
         /// Delete database row using primary_key
         pub async fn delete<E: sqlx::Executor<'c, Database = sqlx::#database_type>>(pool: E, pk: #pk_type ) -> sqlx::Result<#sqlx_qr_path> {
-            sqlx::query!(#query, pk ).execute(pool).await
+            sqlx::query!(#query, #(#pk_values),* ).execute(pool).await
         }
     ]
 }
 
 fn build_sql_query(sqlo: &Sqlo) -> String {
-    let Sqlo {
-        tablename,
-        pk_field,
-        ..
-    } = sqlo;
-    let qmark = qmarks(1);
-    let pk_column = &pk_field.column;
-    format!("DELETE FROM {tablename} WHERE {pk_column}={qmark};")
+    let Sqlo { tablename, .. } = sqlo;
+    let pk_where = pk_where_query(sqlo);
+    format!("DELETE FROM {tablename} WHERE {pk_where};")
 }
@@ -0,0 +1,141 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+
+use crate::{
+    error::SqloError, relations::Relations, sqlo::Sqlo, sqlos::Sqlos, types::get_function_arg_type,
+};
+
+use super::{column_enum::column_enum_ident, pk_arg_values};
+
+/// `sqlo::SqloEntity` implementation, forwarding to the inherent methods.
+pub fn impl_sqlo_entity(sqlo: &Sqlo, sqlos: &Sqlos) -> Result<TokenStream, SqloError> {
+    let Sqlo {
+        ident,
+        tablename,
+        fields,
+        pk_fields,
+        ..
+    } = sqlo;
+
+    let pk_tys = pk_fields.iter().map(|f| &f.ty);
+    let pk_ty = if sqlo.has_composite_pk() {
+        quote! {(#(#pk_tys),*)}
+    } else {
+        quote! {#(#pk_tys)*}
+    };
+    // owned key to the arguments taken by inherent methods
+    let pk_args = pk_arg_values(sqlo, &format_ident!("id"))
+        .into_iter()
+        .zip(pk_fields.iter())
+        .map(|(value, field)| {
+            let arg_ty: syn::Type = syn::parse2(get_function_arg_type(&field.ty))?;
+            Ok(match arg_ty {
+                syn::Type::Reference(_) => quote! {&#value},
+                _ => value,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let pk_arg = if sqlo.has_composite_pk() {
+        quote! {(#(#pk_args),*)}
+    } else {
+        quote! {#(#pk_args)*}
+    };
+
+    let columns = fields.iter().map(|f| &f.column);
+    let pk_columns = pk_fields.iter().map(|f| &f.column);
+
+    let sqlo_relations = Relations::from_sqlo(sqlo);
+    let mut relations = vec![];
+    for relation in sqlo_relations.iter() {
+        let field = sqlo.field(relation.field.as_ident()).ok_or_else(|| {
+            SqloError::new("Sqlo: unknown foreign key field", relation.field.span())
+        })?;
+        let column = &field.column;
+        let field = relation.field.as_str();
+        let to = relation.to.as_str();
+        let to_table = &sqlos.get(&relation.to)?.tablename;
+        let related = relation.related.as_str();
+        relations.push(quote! {
+            ::sqlo::Relation {
+                field: #field,
+                column: #column,
+                to: #to,
+                to_table: #to_table,
+                related: #related,
+            }
+        })
+    }
+
+    let columns_meta = fields.iter().map(|f| {
+        let field_ident = f.ident.as_str();
+        let column = &f.column;
+        let ty = f.ty.to_token_stream().to_string().replace(' ', "");
+        let primary_key = pk_fields.contains(f);
+        let relation = sqlo_relations.iter().find(|r| r.field == f.ident);
+        let fk = option_str(relation.map(|r| r.to.as_str()));
+        let related = option_str(relation.map(|r| r.related.as_str()));
+        quote! {
+            ::sqlo::ColumnMeta {
+                ident: #field_ident,
+                column: #column,
+                ty: #ty,
+                primary_key: #primary_key,
+                fk: #fk,
+                related: #related,
+            }
+        }
+    });
+    let name = ident.as_str();
+    let column_enum = column_enum_ident(sqlo);
+
+    Ok(quote! {
+        impl ::sqlo::SqloEntity for #ident {
+            type Pk = #pk_ty;
+            type Column = #column_enum;
+
+            const TABLE: &'static str = #tablename;
+            const COLUMNS: &'static [&'static str] = &[#(#columns),*];
+            const PK_COLUMNS: &'static [&'static str] = &[#(#pk_columns),*];
+            const RELATIONS: &'static [::sqlo::Relation] = &[#(#relations),*];
+
+            fn sqlo_meta() -> &'static ::sqlo::EntityMeta {
+                static META: ::sqlo::EntityMeta = ::sqlo::EntityMeta {
+                    name: #name,
+                    table: #tablename,
+                    columns: &[#(#columns_meta),*],
+                    primary_key: <#ident as ::sqlo::SqloEntity>::PK_COLUMNS,
+                    relations: <#ident as ::sqlo::SqloEntity>::RELATIONS,
+                };
+                &META
+            }
+
+            fn get<'c, E: sqlx::Executor<'c, Database = ::sqlo::Db>>(
+                pool: E,
+                id: Self::Pk,
+            ) -> impl ::std::future::Future<Output = sqlx::Result<Self>> + Send {
+                async move { Self::get(pool, #pk_arg).await }
+            }
+
+            fn save<'c, E: sqlx::Executor<'c, Database = ::sqlo::Db>>(
+                &self,
+                pool: E,
+            ) -> impl ::std::future::Future<Output = sqlx::Result<<::sqlo::Db as sqlx::Database>::QueryResult>> + Send {
+                Self::save(self, pool)
+            }
+
+            fn delete<'c, E: sqlx::Executor<'c, Database = ::sqlo::Db>>(
+                pool: E,
+                id: Self::Pk,
+            ) -> impl ::std::future::Future<Output = sqlx::Result<<::sqlo::Db as sqlx::Database>::QueryResult>> + Send {
+                async move { Self::delete(pool, #pk_arg).await }
+            }
+        }
+    })
+}
+
+fn option_str(value: Option<&str>) -> TokenStream {
+    match value {
+        Some(value) => quote! {Some(#value)},
+        None => quote! {None},
+    }
+}
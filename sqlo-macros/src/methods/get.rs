@@ -1,7 +1,9 @@
-use crate::database::{db_ident, qmarks};
-use crate::{sqlo::Sqlo, types::get_function_arg_type};
+use crate::database::db_ident;
+use crate::sqlo::Sqlo;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+
+use super::{pk_arg_type, pk_arg_values, pk_where_query};
 
 pub fn impl_get(s: &Sqlo) -> TokenStream {
     let Sqlo {
@@ -11,18 +13,17 @@ pub fn impl_get(s: &Sqlo) -> TokenStream {
         ..
     } = s;
 
-    let pk_ty = get_function_arg_type(&s.pk_field.ty);
+    let pk_ty = pk_arg_type(s);
+    let pk_values = pk_arg_values(s, &format_ident!("id"));
     let database_type = db_ident();
 
-    let pk_column = &s.pk_field.column;
-    let qmarks = qmarks(1);
+    let pk_where = pk_where_query(s);
 
-    let query =
-        format!("SELECT {all_columns_as_query} FROM {tablename} WHERE {pk_column}={qmarks}");
+    let query = format!("SELECT {all_columns_as_query} FROM {tablename} WHERE {pk_where}");
     quote! {
             /// Get instance by its PrimaryKey.
             pub async fn get<E: sqlx::Executor<'c, Database = sqlx::#database_type>>(pool: E, id: #pk_ty) -> sqlx::Result<#ident> {
-                sqlx::query_as!(#ident, #query, id)
+                sqlx::query_as!(#ident, #query, #(#pk_values),*)
                 .fetch_one(pool)
                 .await
             }
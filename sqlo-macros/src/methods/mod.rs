@@ -0,0 +1,45 @@
+pub mod column_enum;
+pub mod delete;
+pub mod entity;
+pub mod get;
+pub mod related;
+pub mod save;
+
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{database::qmarks_with_col, sqlo::Sqlo, types::get_function_arg_type};
+
+/// Sql condition matching every primary key column: `pk1=? AND pk2=?`.
+pub fn pk_where_query(sqlo: &Sqlo) -> String {
+    sqlo.pk_fields
+        .iter()
+        .enumerate()
+        .map(|(skip, f)| qmarks_with_col(skip, &[f.column.as_str()]))
+        .join(" AND ")
+}
+
+/// Primary key type as function argument, a tuple if primary key is composite.
+pub fn pk_arg_type(sqlo: &Sqlo) -> TokenStream {
+    if sqlo.has_composite_pk() {
+        let types = sqlo.pk_fields.iter().map(|f| get_function_arg_type(&f.ty));
+        quote! {(#(#types),*)}
+    } else {
+        get_function_arg_type(&sqlo.pk_fields[0].ty)
+    }
+}
+
+/// Each primary key value taken from the function argument `arg`.
+pub fn pk_arg_values(sqlo: &Sqlo, arg: &syn::Ident) -> Vec<TokenStream> {
+    if sqlo.has_composite_pk() {
+        (0..sqlo.pk_fields.len())
+            .map(|i| {
+                let i = syn::Index::from(i);
+                quote! {#arg.#i}
+            })
+            .collect()
+    } else {
+        vec![quote! {#arg}]
+    }
+}
@@ -0,0 +1,225 @@
+use darling::util::IdentString;
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    database::{db_ident, db_sqlx_path, qmarks_with_col, MAX_BIND_PARAMS},
+    error::SqloError,
+    macros::decode_row,
+    relations::{Relation, Relations},
+    sqlo::Sqlo,
+    sqlos::Sqlos,
+    types::is_type_option,
+};
+
+use super::pk_where_query;
+
+/// Navigation methods of every foreign key of `sqlo`.
+///
+/// Both sides are produced here because the fk struct is the only one knowing the relation:
+/// the first TokenStream goes in `sqlo`'s impl block, the second holds the targets' impl blocks.
+pub fn impl_related(sqlo: &Sqlo, sqlos: &Sqlos) -> Result<(TokenStream, TokenStream), SqloError> {
+    let mut to_parent = TokenStream::new();
+    let mut to_children = TokenStream::new();
+    let mut parent_methods: Vec<IdentString> = vec![];
+    for relation in Relations::from_sqlo(sqlo).iter() {
+        let method = parent_method(relation, sqlo);
+        if parent_methods.contains(&method) {
+            return Err(SqloError::new_spanned(
+                &relation.field,
+                format!(
+                    "`{method}` already gets another parent, choose another name with `parent = \"...\"`"
+                ),
+            ));
+        }
+        parent_methods.push(method);
+        let parent = sqlos.get(&relation.to)?;
+        to_parent.extend(impl_to_parent(relation, sqlo, parent));
+        to_parent.extend(impl_load_for_parents(relation, sqlo, parent)?);
+        if !relation.is_self_join() {
+            to_children.extend(impl_to_children(relation, sqlo, parent)?);
+        }
+    }
+    Ok((to_parent, to_children))
+}
+
+// self join: related is the name used to reach the parent
+fn parent_method(relation: &Relation, child: &Sqlo) -> IdentString {
+    if relation.is_self_join() {
+        relation.related.clone()
+    } else {
+        relation.parent_name(child)
+    }
+}
+
+// room.house(&pool) -> House
+fn impl_to_parent(relation: &Relation, child: &Sqlo, parent: &Sqlo) -> TokenStream {
+    let Sqlo {
+        ident: parent_ident,
+        tablename,
+        all_columns_as_query,
+        ..
+    } = parent;
+    let method = parent_method(relation, child);
+    let field = &relation.field;
+    let database_type = db_ident();
+    let query = format!(
+        "SELECT {all_columns_as_query} FROM {tablename} WHERE {}",
+        pk_where_query(parent)
+    );
+    if is_type_option(&relation.ty) {
+        quote! {
+            /// Get the related row targeted by the foreign key, if any.
+            pub async fn #method<E: sqlx::Executor<'c, Database = sqlx::#database_type>>(&self, pool: E) -> sqlx::Result<Option<#parent_ident>> {
+                sqlx::query_as!(#parent_ident, #query, self.#field)
+                .fetch_optional(pool)
+                .await
+            }
+        }
+    } else {
+        quote! {
+            /// Get the related row targeted by the foreign key.
+            pub async fn #method<E: sqlx::Executor<'c, Database = sqlx::#database_type>>(&self, pool: E) -> sqlx::Result<#parent_ident> {
+                sqlx::query_as!(#parent_ident, #query, self.#field)
+                .fetch_one(pool)
+                .await
+            }
+        }
+    }
+}
+
+// Room::load_for_houses(&pool, &houses) -> HashMap<house_pk, Vec<Room>>
+fn impl_load_for_parents(
+    relation: &Relation,
+    child: &Sqlo,
+    parent: &Sqlo,
+) -> Result<TokenStream, SqloError> {
+    let parent_ident = &parent.ident;
+    let pk_field = parent.pk_field()?;
+    let pk = &pk_field.ident;
+    let pk_ty = &pk_field.ty;
+    // plural of the method getting the parent
+    let method = format_ident!("load_for_{}s", parent_method(relation, child).as_ident());
+    let fk = &relation.field;
+    let fk_column = &child
+        .field(fk.as_ident())
+        .expect("Sqlo Internal Error: relation field must exist")
+        .column;
+    let columns = child.fields.iter().map(|f| f.column.as_str()).join(",");
+    let query = format!(
+        "SELECT {columns} FROM {} WHERE {fk_column} IN (",
+        child.tablename
+    );
+    let database_type = db_ident();
+    let db_path = db_sqlx_path();
+    let decode = decode_row(child);
+    // rows with a NULL fk are never selected
+    let push_child = if is_type_option(&relation.ty) {
+        quote! {
+            if let Some(key) = child.#fk.clone() {
+                res.entry(key).or_default().push(child);
+            }
+        }
+    } else {
+        quote! {
+            res.entry(child.#fk.clone()).or_default().push(child);
+        }
+    };
+    Ok(quote! {
+        /// Get every row targeting one of `parents`, grouped by parent's primary key.
+        ///
+        /// A single query is sent unless parents are more than the bind parameters limit of the database.
+        pub async fn #method<A: sqlx::Acquire<'c, Database = sqlx::#database_type>>(pool: A, parents: &[#parent_ident]) -> sqlx::Result<std::collections::HashMap<#pk_ty, Vec<Self>>> {
+            let mut res: std::collections::HashMap<#pk_ty, Vec<Self>> = parents
+                .iter()
+                .map(|parent| (parent.#pk.clone(), Vec::new()))
+                .collect();
+            if parents.is_empty() {
+                return Ok(res);
+            }
+            let mut conn = pool.acquire().await?;
+            for chunk in parents.chunks(#MAX_BIND_PARAMS) {
+                let mut query_builder = sqlx::QueryBuilder::<#db_path>::new(#query);
+                let mut separated = query_builder.separated(", ");
+                for parent in chunk {
+                    separated.push_bind(parent.#pk.clone());
+                }
+                separated.push_unseparated(")");
+                for row in query_builder.build().fetch_all(&mut *conn).await?.iter() {
+                    let child = #decode;
+                    #push_child
+                }
+            }
+            Ok(res)
+        }
+    })
+}
+
+// house.therooms(&pool) -> Vec<Room>
+fn impl_to_children(
+    relation: &Relation,
+    child: &Sqlo,
+    parent: &Sqlo,
+) -> Result<TokenStream, SqloError> {
+    let Sqlo {
+        ident: child_ident,
+        tablename,
+        all_columns_as_query,
+        ..
+    } = child;
+    let parent_ident = &parent.ident;
+    let parent_pk = &parent.pk_field()?.ident;
+    let method = &relation.related;
+    let fk_column = &child
+        .field(relation.field.as_ident())
+        .expect("Sqlo Internal Error: relation field must exist")
+        .column;
+    let database_type = db_ident();
+    let query = format!(
+        "SELECT {all_columns_as_query} FROM {tablename} WHERE {}",
+        qmarks_with_col(0, &[fk_column])
+    );
+    Ok(quote! {
+        impl<'c> #parent_ident {
+            /// Get every related row pointing to this instance.
+            pub async fn #method<E: sqlx::Executor<'c, Database = sqlx::#database_type>>(&self, pool: E) -> sqlx::Result<Vec<#child_ident>> {
+                sqlx::query_as!(#child_ident, #query, self.#parent_pk)
+                .fetch_all(pool)
+                .await
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test_related {
+    use super::*;
+
+    #[test]
+    fn test_parent_methods_with_the_same_name() {
+        let sqlos = Sqlos::from_derive_inputs(&[
+            syn::parse_quote! {
+                struct Maison {
+                    id: i64,
+                }
+            },
+            syn::parse_quote! {
+                struct Piece {
+                    #[sqlo(primary_key)]
+                    nb: uuid::Uuid,
+                    #[sqlo(fk = "Maison", related = "pieces")]
+                    maison_id: i64,
+                    // the self join reaches its parent with `related`
+                    #[sqlo(fk = "Piece", related = "maison")]
+                    lg: Option<uuid::Uuid>,
+                }
+            },
+        ]);
+        let err = impl_related(sqlos.get("Piece").unwrap(), &sqlos).unwrap_err();
+        assert_eq!(
+            err.msg(),
+            "`maison` already gets another parent, choose another name with `parent = \"...\"`"
+        );
+    }
+}
@@ -10,16 +10,17 @@ use quote::quote;
 pub fn impl_save(sqlo: &Sqlo) -> TokenStream {
     let Sqlo {
         tablename,
-        pk_field,
+        pk_fields,
         fields,
         ..
     } = sqlo;
 
     // prepare column for query
     let q_columns: Vec<&str> = fields.iter().map(|x| x.column.as_str()).collect();
+    let pk_columns: Vec<&str> = pk_fields.iter().map(|x| x.column.as_str()).collect();
     let columns_no_pk = q_columns
         .iter()
-        .filter(|c| c != &&pk_field.column.as_str())
+        .filter(|c| !pk_columns.contains(c))
         .copied()
         .collect::<Vec<_>>();
 
@@ -35,12 +36,7 @@ pub fn impl_save(sqlo: &Sqlo) -> TokenStream {
     let q_self_fields = quote! {#(self.#self_fields),*};
 
     // build query
-    let query = build_sql_query(
-        tablename,
-        &q_columns,
-        &pk_field.column,
-        columns_no_pk.as_slice(),
-    );
+    let query = build_sql_query(tablename, &q_columns, &pk_columns, columns_no_pk.as_slice());
 
     // database identifier stuffs.
     let database_type = db_ident();
@@ -50,7 +46,7 @@ pub fn impl_save(sqlo: &Sqlo) -> TokenStream {
 
             /// Create a new row with instance or update it if pk already exists.
             ///
-            /// It's an UPSERT statement based  on Primary Key.
+            /// It's an UPSERT statement based  on Primary Key (every column if composite).
             pub async fn save<E: sqlx::Executor<'c, Database = sqlx::#database_type>>(&self, pool: E) -> sqlx::Result<#sqlx_qr_path> {
                 sqlx::query!(#query, #q_self_fields )
                 .execute(pool)
@@ -64,31 +60,48 @@ pub fn impl_save(sqlo: &Sqlo) -> TokenStream {
 fn build_sql_query(
     tablename: &str,
     columns_array: &[&str],
-    pk_column: &str,
+    pk_columns: &[&str],
     col_if_update: &[&str],
 ) -> String {
     let mut qmarks = qmarks(columns_array.len());
     if qmarks.is_empty() {
         qmarks = "NULL".to_string();
     }
-    let col_qmarks_if_update = qmarks_with_col(1, col_if_update);
-
-    let on_conflict = if columns_array.len() > 1 {
-        format!("DO UPDATE SET {col_qmarks_if_update}")
-    } else {
-        "DO NOTHING".to_string() //no update if pk exists and is the only field
-    };
+    // postgres reuses insert args: each column points to its own position.
+    let col_qmarks_if_update = col_if_update
+        .iter()
+        .map(|c| {
+            let skip = columns_array.iter().position(|x| x == c).unwrap_or(0);
+            qmarks_with_col(skip, &[c])
+        })
+        .join(",");
+
+    //no update if pk exists and is the only field
+    let on_conflict = on_conflict_query(
+        pk_columns,
+        (columns_array.len() > pk_columns.len()).then_some(col_qmarks_if_update.as_str()),
+    );
 
     let columns = commma_sep_with_parenthes_literal_list(columns_array);
 
-    format!("INSERT INTO {tablename} {columns} VALUES({qmarks}) ON CONFLICT ({pk_column}) {on_conflict};")
+    format!("INSERT INTO {tablename} {columns} VALUES({qmarks}) {on_conflict};")
+}
+
+/// Conflict part of an upsert: update with `updates` or do nothing.
+#[cfg(not(feature = "mysql"))]
+pub fn on_conflict_query(conflict_columns: &[&str], updates: Option<&str>) -> String {
+    let conflict_columns = conflict_columns.join(",");
+    match updates {
+        Some(updates) => format!("ON CONFLICT ({conflict_columns}) DO UPDATE SET {updates}"),
+        None => format!("ON CONFLICT ({conflict_columns}) DO NOTHING"),
+    }
 }
 
 #[cfg(feature = "mysql")]
 fn build_sql_query(
     tablename: &str,
     columns_array: &[&str],
-    _pk_column: &str,
+    pk_columns: &[&str],
     col_if_update: &[&str],
 ) -> String {
     let mut qmarks = qmarks(columns_array.len());
@@ -96,10 +109,28 @@ fn build_sql_query(
         qmarks = "NULL".to_string();
     }
     let col_qmarks_if_update = qmarks_with_col(1, col_if_update);
+    let on_conflict = on_conflict_query(
+        pk_columns,
+        (!col_if_update.is_empty()).then_some(col_qmarks_if_update.as_str()),
+    );
 
     let columns = commma_sep_with_parenthes_literal_list(columns_array);
 
-    format!("INSERT INTO {tablename} {columns} VALUES({qmarks}) ON DUPLICATE KEY UPDATE {col_qmarks_if_update};")
+    format!("INSERT INTO {tablename} {columns} VALUES({qmarks}) {on_conflict};")
+}
+
+/// Conflict part of an upsert: update with `updates` or do nothing.
+/// MySQL can't target columns, any unique key conflicts.
+#[cfg(feature = "mysql")]
+pub fn on_conflict_query(conflict_columns: &[&str], updates: Option<&str>) -> String {
+    match updates {
+        Some(updates) => format!("ON DUPLICATE KEY UPDATE {updates}"),
+        // every column is part of the primary key: nothing to update
+        None => {
+            let col = conflict_columns[0];
+            format!("ON DUPLICATE KEY UPDATE {col}={col}")
+        }
+    }
 }
 
 fn commma_sep_with_parenthes_literal_list(list: &[&str]) -> String {
@@ -115,7 +146,7 @@ fn commma_sep_with_parenthes_literal_list(list: &[&str]) -> String {
 fn update_args(sqlo: &Sqlo) -> Vec<&IdentString> {
     sqlo.fields
         .iter()
-        .filter(|f| f.ident != sqlo.pk_field.ident)
+        .filter(|f| !sqlo.pk_fields.contains(f))
         .map(|f| &f.ident)
         .collect::<Vec<_>>()
 }
@@ -131,15 +162,37 @@ mod crud_save {
     use super::*;
     #[test]
     fn test_save_sql_args_query_builder() {
-        assert_eq!(build_sql_query("latable", &["un","deux"], "lepk", &["col","if","update"]),
+        assert_eq!(build_sql_query("latable", &["un","deux"], &["lepk"], &["col","if","update"]),
         "INSERT INTO latable (un,deux) VALUES(?,?) ON CONFLICT (lepk) DO UPDATE SET col=?,if=?,update=?;")
     }
+
+    #[test]
+    fn test_save_sql_args_query_builder_composite_pk() {
+        assert_eq!(build_sql_query("latable", &["un","deux", "trois"], &["un", "deux"], &["trois"]),
+        "INSERT INTO latable (un,deux,trois) VALUES(?,?,?) ON CONFLICT (un,deux) DO UPDATE SET trois=?;");
+        assert_eq!(
+            build_sql_query("latable", &["un", "deux"], &["un", "deux"], &[]),
+            "INSERT INTO latable (un,deux) VALUES(?,?) ON CONFLICT (un,deux) DO NOTHING;"
+        )
+    }
+    #[test]
+    fn test_on_conflict_query() {
+        assert_eq!(
+            on_conflict_query(&["un", "deux"], Some("trois=excluded.trois")),
+            "ON CONFLICT (un,deux) DO UPDATE SET trois=excluded.trois"
+        );
+        assert_eq!(
+            on_conflict_query(&["un"], None),
+            "ON CONFLICT (un) DO NOTHING"
+        );
+    }
+
     macro_rules! test_save_build_query {
         ($titre:literal, [$($cols:literal),*], $res:literal) => {
             paste::paste! {
                 #[test]
                 fn [<save_query_builder _ $titre>]() {
-                    assert_eq!(build_sql_query("bla", &[$(&$cols),*], "pk", &["set","col"]), $res)
+                    assert_eq!(build_sql_query("bla", &[$(&$cols),*], &["pk"], &["set","col"]), $res)
                 }
             }
         };
@@ -0,0 +1,158 @@
+use crate::{
+    field::{Field, FieldParser},
+    relations::{as_related_name, ManyToMany},
+};
+use darling::{util::IdentString, FromDeriveInput, FromMeta};
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(sqlo), supports(struct_named))]
+pub struct SqloParse {
+    pub ident: syn::Ident,
+    data: darling::ast::Data<(), FieldParser>,
+    tablename: Option<String>,
+    #[darling(default)]
+    pub parse_only: bool,
+    #[darling(multiple)]
+    many_to_many: Vec<ManyToManyParse>,
+}
+
+/// `many_to_many(target = "Tag", through = "HouseTag", related = "tags")`
+#[derive(Debug, FromMeta)]
+struct ManyToManyParse {
+    target: IdentString,
+    through: IdentString,
+    related: Option<IdentString>,
+}
+
+// parser methods
+impl SqloParse {
+    pub fn has_pk_fields(&self) -> syn::Result<Vec<Field>> {
+        let fields = self.fields()?;
+        let pk_fields = fields
+            .iter()
+            .filter(|f| f.primary_key)
+            .cloned()
+            .collect::<Vec<_>>();
+        if pk_fields.len() > 1 {
+            // composite primary key
+            if let Some(f) = pk_fields.iter().find(|f| f.insert_fn.is_some()) {
+                return Err(syn::Error::new_spanned(
+                    &f.insert_fn,
+                    "`insert_fn` is not supported with composite primary key",
+                ));
+            }
+            return Ok(pk_fields);
+        }
+        if !pk_fields.is_empty() {
+            return Ok(pk_fields);
+        }
+        for f in &fields {
+            if f.ident == "id" {
+                return Ok(vec![f.clone()]);
+            }
+        }
+        Err(syn::Error::new(
+            self.ident.span(),
+            "Sqlo should have one field with attirbute `primary_key` or an `id` field!",
+        ))
+    }
+
+    pub fn tablename(&self) -> String {
+        if let Some(ref tablename) = self.tablename {
+            tablename.to_string()
+        } else {
+            heck::AsSnakeCase(self.ident.to_string()).to_string()
+        }
+    }
+
+    pub fn fields(&self) -> syn::Result<Vec<Field>> {
+        self.data
+            .clone()
+            .take_struct()
+            .expect("should never fail")
+            .fields
+            .into_iter()
+            .map(|f| f.try_into())
+            .collect()
+    }
+
+    pub fn many_to_many(&self) -> syn::Result<Vec<ManyToMany>> {
+        let mut relations: Vec<ManyToMany> = vec![];
+        for m2m in &self.many_to_many {
+            let related = m2m
+                .related
+                .clone()
+                .unwrap_or_else(|| as_related_name(&m2m.target));
+            if relations.iter().any(|r| r.related == related) {
+                return Err(syn::Error::new_spanned(
+                    &related,
+                    format!("related name `{related}` is already used by another many_to_many, choose another one with `related = \"...\"`"),
+                ));
+            }
+            relations.push(ManyToMany {
+                from: self.ident.clone().into(),
+                related,
+                through: m2m.through.clone(),
+                to: m2m.target.clone(),
+            });
+        }
+        Ok(relations)
+    }
+
+    pub fn all_columns_as_query(fields: &[Field], tablename: &str) -> String {
+        let mut res = vec![];
+        for f in fields.iter() {
+            // we write full query if name or type isn't the same between rust struct and database
+            if f.type_override || f.ident != f.column || f.ident == "id" {
+                let a =
+                    format!(r#"{}.{} as "{}:_""#, tablename, &f.column, &f.ident).replace('\\', "");
+                res.push(a);
+            } else {
+                let a = format!("{}.{}", tablename, f.column);
+                res.push(a)
+            }
+        }
+        res.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn test_many_to_many_is_repeatable() {
+        let sp = SqloParse::from_derive_input(&syn::parse_quote! {
+            #[sqlo(
+                many_to_many(target = "Tag", through = "HouseTag"),
+                many_to_many(target = "Tag", through = "HouseOtherTag", related = "others")
+            )]
+            struct House {
+                id: i64,
+            }
+        })
+        .unwrap();
+        let relations = sp.many_to_many().unwrap();
+        assert_eq!(relations[0].related, "tag");
+        assert_eq!(relations[1].related, "others");
+        assert_eq!(relations[1].through, "HouseOtherTag");
+    }
+
+    #[test]
+    fn test_many_to_many_related_names_are_unique() {
+        let sp = SqloParse::from_derive_input(&syn::parse_quote! {
+            #[sqlo(
+                many_to_many(target = "Tag", through = "HouseTag"),
+                many_to_many(target = "Tag", through = "HouseOtherTag")
+            )]
+            struct House {
+                id: i64,
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            sp.many_to_many().unwrap_err().to_string(),
+            "related name `tag` is already used by another many_to_many, choose another one with `related = \"...\"`"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    error::SqloError,
+    methods::{
+        column_enum::impl_column_enum, delete::impl_delete, entity::impl_sqlo_entity,
+        get::impl_get, related::impl_related, save::impl_save,
+    },
+    sqlo::Sqlo,
+    sqlos::Sqlos,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub fn produce(sqlo: &Sqlo, sqlos: &Sqlos) -> Result<TokenStream, SqloError> {
+    let ident = sqlo.ident.clone();
+    let additional_utils = impl_additional_utils(sqlo);
+    let crud_queries = impl_crud_queries(sqlo);
+    let (to_parent, to_children) = impl_related(sqlo, sqlos)?;
+    let sqlo_entity = impl_sqlo_entity(sqlo, sqlos)?;
+    let column_enum = impl_column_enum(sqlo);
+
+    Ok(quote! {
+        impl <'c>#ident {
+            #additional_utils
+            #crud_queries
+            #to_parent
+        }
+
+        #sqlo_entity
+
+        #column_enum
+
+        #to_children
+    })
+}
+
+fn impl_crud_queries(sqlo: &Sqlo) -> TokenStream {
+    let get = impl_get(sqlo);
+    let save = impl_save(sqlo);
+    let delete = impl_delete(sqlo);
+    quote!(
+            #get
+            #save
+            #delete
+    )
+}
+
+fn impl_additional_utils(s: &Sqlo) -> TokenStream {
+    let Sqlo {
+        ident, pk_fields, ..
+    } = s;
+    let pk_idents = pk_fields.iter().map(|f| &f.ident);
+    let pk_tys = pk_fields.iter().map(|f| &f.ty);
+    let pk = if s.has_composite_pk() {
+        quote! {
+            pub fn pk(&self) -> (#(&#pk_tys),*) {
+                (#(&self.#pk_idents),*)
+            }
+        }
+    } else {
+        quote! {
+            pub fn pk(&self) -> #(&#pk_tys)* {
+                #(&self.#pk_idents)*
+            }
+        }
+    };
+    let ident_name = LitStr::new(ident.as_str(), ident.span());
+    quote! {
+        #pk
+
+        pub fn sqlo_struct_name(&self) -> &str {
+            #ident_name
+        }
+
+
+    }
+}
@@ -0,0 +1,690 @@
+use darling::util::IdentString;
+use proc_macro2::Span;
+use quote::format_ident;
+use regex::Regex;
+use syn::{AngleBracketedGenericArguments, GenericArgument, PathArguments, Type, TypePath};
+
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::SqloError, field::Field, macros::Generator, sqlo::Sqlo, sqlos::Sqlos};
+
+// not const so we can use it in macro
+// patter: struct, field, fk_struct, fk_related_name, type
+macro_rules! fk_pattern {
+    () => {
+        "fk-{}--{}---{}--{}----{}"
+    };
+}
+
+// patter: struct, related, through_struct, target_struct
+macro_rules! m2m_pattern {
+    () => {
+        "m2m-{}--{}---{}----{}"
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Relations {
+    fks: Vec<Relation>,
+    many_to_many: Vec<ManyToMany>,
+}
+
+impl FromIterator<Relation> for Relations {
+    fn from_iter<T: IntoIterator<Item = Relation>>(iter: T) -> Self {
+        Self {
+            fks: iter.into_iter().collect::<Vec<Relation>>(),
+            many_to_many: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+impl FromIterator<Relations> for Relations {
+    fn from_iter<T: IntoIterator<Item = Relations>>(iter: T) -> Self {
+        iter.into_iter().fold(Relations::default(), |mut acc, r| {
+            acc.fks.extend(r.fks);
+            acc.many_to_many.extend(r.many_to_many);
+            acc
+        })
+    }
+}
+
+impl Relations {
+    /// Build new Relations from a Sqlo instance.
+    /// Span is preserved for field
+    pub fn from_sqlo(sqlo: &Sqlo) -> Relations {
+        let mut relations: Relations = sqlo
+            .fields
+            .iter()
+            .flat_map(|f| make_field_relations(f, sqlo))
+            .collect();
+        relations.many_to_many = sqlo.many_to_many.clone();
+        relations
+    }
+
+    /// get all relations present in a dir
+    /// No span is preserved
+    pub fn from_path(path: &Path) -> std::io::Result<Self> {
+        let mut relations = vec![];
+
+        // foreignkeys
+        let pat = path.join(format!(fk_pattern!(), "*", "*", "*", "*", "*"));
+        let fk_relations: Vec<PathBuf> = glob::glob(pat.to_str().unwrap())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.msg))?
+            .filter_map(Result::ok)
+            .collect();
+        relations.extend_from_slice(&fk_relations);
+        let mut res: Relations = relations
+            .into_iter()
+            .filter_map(|f| Relation::try_from(f).ok())
+            .collect();
+
+        // many to many
+        let pat = path.join(format!(m2m_pattern!(), "*", "*", "*", "*"));
+        res.many_to_many = glob::glob(pat.to_str().unwrap())
+            .map_err(|e| io::Error::other(e.msg))?
+            .filter_map(Result::ok)
+            .filter_map(|f| ManyToMany::try_from(f).ok())
+            .collect();
+        Ok(res)
+    }
+
+    /// Turn relation to a Vec of Relation using thier PathBuf representation.
+    pub fn to_files(&self) -> Vec<PathBuf> {
+        self.fks
+            .iter()
+            .map(|r| PathBuf::from(r.to_string()))
+            .chain(
+                self.many_to_many
+                    .iter()
+                    .map(|r| PathBuf::from(r.to_string())),
+            )
+            .collect()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Relation> {
+        self.fks.iter()
+    }
+
+    pub fn filter_entity(self, mode: &str, ident: &str) -> Relations {
+        let keep = |from: &IdentString, to: &IdentString| {
+            if mode == "from" {
+                *from == ident
+            } else if mode == "to" {
+                *to == ident
+            } else if mode == "both" {
+                *from == ident || *to == ident
+            } else {
+                unreachable!("Only `to` and `from` are allowed")
+            }
+        };
+        Relations {
+            fks: self
+                .fks
+                .into_iter()
+                .filter(|f| keep(&f.from, &f.to))
+                .collect(),
+            many_to_many: self
+                .many_to_many
+                .into_iter()
+                .filter(|f| keep(&f.from, &f.to))
+                .collect(),
+        }
+    }
+
+    /// Returns PathPub of other not contained in self.
+    pub fn difference_of_other(&self, other: &[PathBuf]) -> Vec<PathBuf> {
+        let list = self.to_files();
+        other
+            .iter()
+            .filter(|x| !list.contains(x))
+            .cloned()
+            .collect()
+    }
+
+    pub fn validate(&self, sqlo: &Sqlo, sqlos: &Sqlos) -> Result<(), SqloError> {
+        let mut parent_names: Vec<IdentString> = vec![];
+        for relation in self.fks.iter() {
+            let relation = relation;
+            relation.validate(sqlo, sqlos)?;
+            // the parent name identifies the relation in queries
+            let parent_name = relation.parent_name(sqlo);
+            if parent_names.contains(&parent_name) {
+                return Err(SqloError::new_spanned(
+                    &relation.field,
+                    format!(
+                        "parent name `{parent_name}` is already used by another foreign key, choose another one with `parent = \"...\"`"
+                    ),
+                ));
+            }
+            parent_names.push(parent_name);
+        }
+        for relation in self.many_to_many.iter() {
+            relation.validate(sqlo, sqlos)?;
+        }
+        Ok(())
+    }
+}
+
+// query impl block
+impl Relations {
+    pub fn find(&self, to: &IdentString, related: &IdentString) -> Result<&Relation, SqloError> {
+        match self
+            .fks
+            .iter()
+            .find(|r| &r.to == to && &r.related == related)
+        {
+            Some(r) => Ok(r),
+            None => Err(SqloError::new("No relation found", related.span())),
+        }
+    }
+
+    pub fn find_many_to_many(
+        &self,
+        from: &IdentString,
+        related: &IdentString,
+    ) -> Result<&ManyToMany, SqloError> {
+        match self
+            .many_to_many
+            .iter()
+            .find(|r| &r.from == from && &r.related == related)
+        {
+            Some(r) => Ok(r),
+            None => Err(SqloError::new("No relation found", related.span())),
+        }
+    }
+}
+
+impl TryFrom<PathBuf> for Relation {
+    type Error = std::io::Error;
+
+    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
+        lazy_static::lazy_static! {
+            static ref RE_FK: Regex = Regex::new(r"^fk-(\w+)--(\w+)---(\w+)--(\w+)----([\w~<>]+)$").unwrap();
+        }
+        if let Some(filename) = value.file_name() {
+            if let Some(filestr) = filename.to_str() {
+                if let Some(captures) = RE_FK.captures(filestr) {
+                    let res = captures
+                        .iter()
+                        .skip(1)
+                        .flatten()
+                        .map(|m| m.as_str())
+                        .collect::<Vec<_>>();
+
+                    if res.len() == 5 {
+                        return Ok(Relation {
+                            from: syn::Ident::new(res[0], Span::call_site()).into(),
+                            field: syn::Ident::new(res[1], Span::call_site()).into(),
+                            to: syn::Ident::new(res[2], Span::call_site()).into(),
+                            related: syn::Ident::new(res[3], Span::call_site()).into(),
+                            ty: syn::parse_str(&res[4].replace('~', ":")).map_err(|_| {
+                                SqloError::new_lost("Could not parse relation type")
+                            })?,
+                        });
+                    }
+                }
+            }
+        }
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Sqlo Could not get relation from filename",
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relation {
+    pub from: IdentString,    // Sqlo struct where fk is defined
+    pub field: IdentString,   // in which field
+    pub ty: syn::TypePath,    // in which type
+    pub to: IdentString,      // Sqlo target struct
+    pub related: IdentString, // identifier used by `to` to access `from`
+}
+
+impl Relation {
+    fn validate(&self, sqlo: &Sqlo, sqlos: &Sqlos) -> Result<(), SqloError> {
+        self.validate_existing_fk_struct(sqlo, sqlos)?; // should always be called first (use of unwrap later)
+        self.validate_existing_fk_type(sqlo, sqlos)?;
+        self.validate_no_field_has_the_same_name_as_related(sqlo, sqlos)?;
+        Ok(())
+    }
+    fn validate_existing_fk_struct(&self, _sqlo: &Sqlo, sqlos: &Sqlos) -> Result<(), SqloError> {
+        let _matching_sqlo = sqlos.get(&self.to)?;
+        Ok(())
+    }
+
+    fn validate_existing_fk_type(&self, _sqlo: &Sqlo, sqlos: &Sqlos) -> Result<(), SqloError> {
+        let matching_sqlo = sqlos.get(&self.to)?;
+        let pk_field = matching_sqlo.single_pk_field().ok_or_else(|| {
+            SqloError::new_spanned(
+                &self.ty,
+                "Foreign key targeting a composite primary key is not supported",
+            )
+        })?;
+
+        if !is_the_same_type_or_option(&pk_field.ty, &self.ty) {
+            return Err(SqloError::new_spanned(
+                &self.ty,
+                format!(
+                    "Field type an foreign key field's type don't match. Expected {} found {}",
+                    format_path(&pk_field.ty.path),
+                    format_path(&self.ty.path)
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_no_field_has_the_same_name_as_related(
+        &self,
+        _sqlo: &Sqlo,
+        sqlos: &Sqlos,
+    ) -> Result<(), SqloError> {
+        let matching_sqlo = sqlos.get(&self.to)?;
+        for field in &matching_sqlo.fields {
+            if field.ident == self.related {
+                return Err(SqloError::new(
+                    "related name must be different from all targeted sqlos's fields",
+                    self.related.span(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Identifier used by `from` to access `to`: the `parent` attribute
+    /// or the snake_case `to` name.
+    pub fn parent_name(&self, from: &Sqlo) -> IdentString {
+        from.field(self.field.as_ident())
+            .and_then(|f| f.parent.clone())
+            .unwrap_or_else(|| as_related_name(&self.to))
+    }
+
+    pub fn is_self_join(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// Join from `source`, the alias of `to`, to the `from` table aliased as `target`.
+    pub fn to_join(
+        &self,
+        source: &IdentString,
+        target: &IdentString,
+        join: Join,
+        ctx: &mut Generator,
+    ) -> Result<String, SqloError> {
+        let to_pk_field = ctx.sqlos.get(&self.to)?.pk_field()?;
+
+        ctx.tables.insert_alias(target, &self.from);
+
+        let tablename_plus_alias = ctx.tables.tablename_with_alias(target)?;
+        let lhs;
+        let rhs;
+        if !self.is_self_join() {
+            lhs = ctx.tables.alias_dot_column(target, &self.field)?;
+            rhs = ctx.tables.alias_dot_column(source, &to_pk_field.ident)?;
+        } else {
+            rhs = ctx.tables.alias_dot_column(target, &to_pk_field.ident)?;
+            lhs = ctx.tables.alias_dot_column(source, &self.field)?;
+        }
+
+        Ok(format!(
+            " {} JOIN {} ON {}={}",
+            join, tablename_plus_alias, lhs, rhs,
+        ))
+    }
+}
+
+impl Relation {
+    // reverse join: from `source`, the alias of the fk struct, to the targeted struct aliased as `target`.
+    pub fn to_parent_join(
+        &self,
+        source: &IdentString,
+        target: &IdentString,
+        join: Join,
+        ctx: &mut Generator,
+    ) -> Result<String, SqloError> {
+        let to_pk_field = ctx.sqlos.get(&self.to)?.pk_field()?;
+
+        ctx.tables.insert_alias(target, &self.to);
+
+        let tablename_plus_alias = ctx.tables.tablename_with_alias(target)?;
+        let lhs = ctx.tables.alias_dot_column(source, &self.field)?;
+        let rhs = ctx.tables.alias_dot_column(target, &to_pk_field.ident)?;
+
+        Ok(format!(
+            " {} JOIN {} ON {}={}",
+            join, tablename_plus_alias, lhs, rhs,
+        ))
+    }
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ty = darling::util::path_to_string(&self.ty.path).replace("::", "~~");
+        write!(
+            f,
+            fk_pattern!(),
+            self.from, self.field, self.to, self.related, ty
+        )
+    }
+}
+
+/// Relation from `from` to `to` going through the join struct `through`,
+/// which holds a foreign key to both of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManyToMany {
+    pub from: IdentString,    // Sqlo struct where many_to_many is declared
+    pub related: IdentString, // identifier used by `from` to access `to`
+    pub through: IdentString, // Sqlo join struct
+    pub to: IdentString,      // Sqlo target struct
+}
+
+impl ManyToMany {
+    fn validate(&self, sqlo: &Sqlo, sqlos: &Sqlos) -> Result<(), SqloError> {
+        if self.from == self.to {
+            return Err(SqloError::new(
+                "many_to_many targeting the same struct is not supported",
+                self.to.span(),
+            ));
+        }
+        if sqlo.field(self.related.as_ident()).is_some() {
+            return Err(SqloError::new(
+                "related name must be different from all sqlo's fields",
+                self.related.span(),
+            ));
+        }
+        // the join struct needs `from` to be derived first, so it may not be known yet.
+        if let Ok(through) = sqlos.get(&self.through) {
+            self.through_fields(through)?;
+        }
+        Ok(())
+    }
+
+    /// Fields of the join struct targeting `from` and `to`.
+    fn through_fields<'a>(&self, through: &'a Sqlo) -> Result<(&'a Field, &'a Field), SqloError> {
+        let find = |target: &IdentString| {
+            let mut fks = through
+                .fields
+                .iter()
+                .filter(|f| f.fk.as_ref() == Some(target));
+            match (fks.next(), fks.next()) {
+                (Some(field), None) => Ok(field),
+                _ => Err(SqloError::new(
+                    &format!(
+                        "`{}` must have exactly one foreign key to `{}`",
+                        self.through, target
+                    ),
+                    self.through.span(),
+                )),
+            }
+        };
+        Ok((find(&self.from)?, find(&self.to)?))
+    }
+
+    // from -> through -> to: `source` is the alias of `from` and `to` is aliased as `target`.
+    pub fn to_join(
+        &self,
+        source: &IdentString,
+        target: &IdentString,
+        join: Join,
+        ctx: &mut Generator,
+    ) -> Result<String, SqloError> {
+        let sqlos = ctx.sqlos;
+        let (from_field, to_field) = self.through_fields(sqlos.get(&self.through)?)?;
+        let from_pk_field = sqlos.get(&self.from)?.pk_field()?;
+        let to_pk_field = sqlos.get(&self.to)?.pk_field()?;
+
+        // the join table is specific to each target
+        let through: IdentString =
+            format_ident!("{}__{}", target.as_ident(), self.through.as_ident()).into();
+        ctx.tables.insert_alias(&through, &self.through);
+        ctx.tables.insert_alias(target, &self.to);
+
+        let through_plus_alias = ctx.tables.tablename_with_alias(&through)?;
+        let to_plus_alias = ctx.tables.tablename_with_alias(target)?;
+        let through_lhs = ctx.tables.alias_dot_column(&through, &from_field.ident)?;
+        let through_rhs = ctx.tables.alias_dot_column(source, &from_pk_field.ident)?;
+        let to_lhs = ctx.tables.alias_dot_column(target, &to_pk_field.ident)?;
+        let to_rhs = ctx.tables.alias_dot_column(&through, &to_field.ident)?;
+
+        // both joins in the same string so their order is kept
+        Ok(format!(
+            " {} JOIN {} ON {}={} {} JOIN {} ON {}={}",
+            join, through_plus_alias, through_lhs, through_rhs, join, to_plus_alias, to_lhs, to_rhs
+        ))
+    }
+
+    /// Join from `to` to `through` when `to` is the main sqlo (`House[1].tags`).
+    /// Also returns the `through` field to compare with `from`'s primary key.
+    pub fn to_through_join(&self, ctx: &mut Generator) -> Result<(String, IdentString), SqloError> {
+        let sqlos = ctx.sqlos;
+        let (from_field, to_field) = self.through_fields(sqlos.get(&self.through)?)?;
+        let to_pk_field = sqlos.get(&self.to)?.pk_field()?;
+
+        ctx.tables.insert_alias(&self.through, &self.through);
+
+        let through_plus_alias = ctx.tables.tablename_with_alias(&self.through)?;
+        let lhs = ctx
+            .tables
+            .alias_dot_column(&self.through, &to_field.ident)?;
+        let rhs = ctx.tables.alias_dot_column(&self.to, &to_pk_field.ident)?;
+
+        Ok((
+            format!(" INNER JOIN {} ON {}={}", through_plus_alias, lhs, rhs),
+            from_field.ident.clone(),
+        ))
+    }
+}
+
+impl TryFrom<PathBuf> for ManyToMany {
+    type Error = std::io::Error;
+
+    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
+        lazy_static::lazy_static! {
+            static ref RE_M2M: Regex = Regex::new(r"^m2m-(\w+)--(\w+)---(\w+)----(\w+)$").unwrap();
+        }
+        if let Some(captures) = value
+            .file_name()
+            .and_then(|f| f.to_str())
+            .and_then(|f| RE_M2M.captures(f))
+        {
+            let res = captures
+                .iter()
+                .skip(1)
+                .flatten()
+                .map(|m| IdentString::new(syn::Ident::new(m.as_str(), Span::call_site())))
+                .collect::<Vec<_>>();
+            if res.len() == 4 {
+                return Ok(ManyToMany {
+                    from: res[0].clone(),
+                    related: res[1].clone(),
+                    through: res[2].clone(),
+                    to: res[3].clone(),
+                });
+            }
+        }
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Sqlo Could not get many to many relation from filename",
+        ))
+    }
+}
+
+impl Display for ManyToMany {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            m2m_pattern!(),
+            self.from, self.related, self.through, self.to
+        )
+    }
+}
+
+fn make_field_relations(field: &Field, sqlo: &Sqlo) -> Vec<Relation> {
+    let mut res = vec![];
+
+    if let Some(ref rel) = field.fk {
+        let related = if let Some(related) = &field.related {
+            related.clone()
+        } else {
+            //adjust span if no related is given
+            let mut ident = sqlo.ident.as_ident().clone();
+            ident.set_span(rel.span());
+            as_related_name(&IdentString::new(ident))
+        };
+        res.push(Relation {
+            from: sqlo.ident.clone(),
+            field: field.ident.clone(),
+            ty: field.ty.clone(),
+            to: rel.clone(),
+            related,
+        })
+    }
+    res
+}
+
+pub fn as_related_name(ident: &IdentString) -> IdentString {
+    use heck::ToSnakeCase;
+    let name = ident.to_string().to_snake_case();
+    format_ident!("{}", syn::Ident::new(&name, ident.span())).into()
+}
+
+fn is_the_same_type_or_option(base: &TypePath, target: &TypePath) -> bool {
+    if base.path == target.path {
+        return true;
+    }
+    for seg in &target.path.segments {
+        if seg.ident == "Option" {
+            if let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) =
+                &seg.arguments
+            {
+                if let Some(GenericArgument::Type(Type::Path(TypePath { path, .. }))) = args.first()
+                {
+                    return path == &base.path;
+                }
+            }
+        }
+    }
+    false
+}
+
+fn format_path(path: &syn::Path) -> String {
+    if let Some(ident) = path.get_ident() {
+        return ident.to_string();
+    }
+    let mut res: Vec<String> = vec![];
+
+    for seg in &path.segments {
+        let ident = seg.ident.to_string();
+        match &seg.arguments {
+            PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) => {
+                if let Some(GenericArgument::Type(Type::Path(TypePath { path, .. }))) = args.first()
+                {
+                    res.push(format!("{}<{}>", ident, format_path(path)))
+                }
+            }
+            PathArguments::None => res.push(ident),
+            _ => unimplemented!("Sqlo: only standard path an option are implemented now"),
+        }
+    }
+    res.iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<String>>()
+        .join("::")
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Join {
+    Inner,
+    Left,
+}
+
+impl Display for Join {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inner => write!(f, "INNER"),
+            Self::Left => write!(f, "LEFT"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_relations {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_pathbug_for_relation() {
+        let p = PathBuf::from_str(&format!(fk_pattern!(), "Aaa", "f", "Bbb", "g", "i32")).unwrap();
+        let _: Relation = p.try_into().unwrap();
+    }
+
+    #[test]
+    fn test_try_from_pathbug_for_relation_with_tilde_in_type() {
+        let p = PathBuf::from_str(&format!(fk_pattern!(), "Aaa", "f", "Bbb", "g", "path~~i32"))
+            .unwrap();
+        let _: Relation = p.try_into().unwrap();
+    }
+
+    #[test]
+    fn test_try_from_pathbug_for_relation_with_sqare_bracket() {
+        let p = PathBuf::from_str(&format!(
+            fk_pattern!(),
+            "Aaa", "f", "Bbb", "g", "Option<i32>"
+        ))
+        .unwrap();
+        let relation: Relation = p.try_into().unwrap();
+        assert_eq!(format_path(&relation.ty.path), "Option<i32>");
+    }
+
+    #[test]
+    fn test_try_from_pathbuf_for_many_to_many() {
+        let p =
+            PathBuf::from_str(&format!(m2m_pattern!(), "Aaa", "bbbs", "AaaBbb", "Bbb")).unwrap();
+        let relation: ManyToMany = p.clone().try_into().unwrap();
+        assert_eq!(relation.through, "AaaBbb");
+        assert_eq!(PathBuf::from(relation.to_string()), p);
+    }
+
+    #[test]
+    fn test_parent_names_are_unique() {
+        let sqlos = Sqlos::from_derive_inputs(&[
+            syn::parse_quote! {
+                struct Maison {
+                    id: i64,
+                }
+            },
+            syn::parse_quote! {
+                struct Piece {
+                    #[sqlo(primary_key)]
+                    nb: uuid::Uuid,
+                    #[sqlo(fk = "Maison", related = "pieces")]
+                    maison_id: i64,
+                    #[sqlo(fk = "Maison", related = "autres_pieces")]
+                    lg: i64,
+                }
+            },
+        ]);
+        let piece = sqlos.get("Piece").unwrap();
+        let err = Relations::from_sqlo(piece)
+            .validate(piece, &sqlos)
+            .unwrap_err();
+        assert_eq!(
+            err.msg(),
+            "parent name `maison` is already used by another foreign key, choose another one with `parent = \"...\"`"
+        );
+    }
+
+    #[test]
+    fn test_fk_pattern_is_not_many_to_many() {
+        let p = PathBuf::from_str(&format!(fk_pattern!(), "Aaa", "f", "Bbb", "g", "i32")).unwrap();
+        assert!(ManyToMany::try_from(p).is_err());
+    }
+}
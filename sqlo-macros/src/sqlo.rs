@@ -1,4 +1,7 @@
-use crate::{field::Field, parse::SqloParse, serdable::IdentStringSer, types::is_type_option};
+use crate::{
+    error::SqloError, field::Field, parse::SqloParse, relations::ManyToMany,
+    serdable::IdentStringSer, types::is_type_option,
+};
 use darling::util::IdentString;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -7,9 +10,15 @@ pub struct Sqlo {
     pub ident: IdentString,
     pub fields: Vec<Field>,
     pub tablename: String,
-    pub pk_field: Field,
+    pub pk_fields: Vec<Field>,
     pub parse_only: bool,
     pub all_columns_as_query: String,
+    // stored as a relation file, only needed while deriving
+    #[serde(skip)]
+    pub many_to_many: Vec<ManyToMany>,
+    // built from the columns of a query, only lives while expanding a macro
+    #[serde(skip)]
+    pub pseudo: bool,
 }
 
 impl TryFrom<SqloParse> for Sqlo {
@@ -18,13 +27,16 @@ impl TryFrom<SqloParse> for Sqlo {
         let tablename = sp.tablename();
         let fields = sp.fields()?;
         let all_columns_as_query = SqloParse::all_columns_as_query(fields.as_slice(), &tablename);
+        let many_to_many = sp.many_to_many()?;
         Ok(Self {
             fields,
             tablename,
-            pk_field: sp.has_pk_field()?,
+            pk_fields: sp.has_pk_fields()?,
             ident: sp.ident.into(),
             parse_only: sp.parse_only,
             all_columns_as_query,
+            many_to_many,
+            pseudo: false,
         })
     }
 }
@@ -49,4 +61,31 @@ impl Sqlo {
     pub fn field(&self, name: &syn::Ident) -> Option<&Field> {
         self.fields.iter().find(|f| f.ident.as_ident() == name)
     }
+
+    /// Is the primary key made of many columns.
+    pub fn has_composite_pk(&self) -> bool {
+        self.pk_fields.len() > 1
+    }
+
+    /// Get the primary key field if not composite.
+    pub fn single_pk_field(&self) -> Option<&Field> {
+        if self.has_composite_pk() {
+            None
+        } else {
+            self.pk_fields.first()
+        }
+    }
+
+    /// Get the primary key field, fails if primary key is composite.
+    pub fn pk_field(&self) -> Result<&Field, SqloError> {
+        self.single_pk_field().ok_or_else(|| {
+            SqloError::new(
+                &format!(
+                    "Sqlo: composite primary key of `{}` is not supported here",
+                    self.ident
+                ),
+                self.ident.span(),
+            )
+        })
+    }
 }
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use darling::util::IdentString;
+
+use crate::{
+    error::SqloError,
+    relations::{ManyToMany, Relation, Relations},
+    sqlo::Sqlo,
+};
+
+#[derive(Debug, Clone)]
+pub struct Sqlos {
+    pub(crate) entities: Vec<Sqlo>,
+    pub(crate) relations: Relations,
+}
+
+impl Sqlos {
+    pub fn get<T: AsRef<str> + Display>(&self, name: T) -> Result<&Sqlo, SqloError> {
+        self.entities
+            .iter()
+            .find(|s| s.ident == name.as_ref())
+            .ok_or_else(|| SqloError::new_lost(&format!("Can't find Sqlo struct `{}`", name)))
+    }
+
+    pub fn get_by_relation(
+        &self,
+        to: &IdentString,
+        related: &IdentString,
+    ) -> Result<&Sqlo, SqloError> {
+        let relation = self.relations.find(to, related)?;
+        self.get(&relation.from)
+    }
+
+    pub fn get_relation(
+        &self,
+        to: &IdentString,
+        related: &IdentString,
+    ) -> Result<&Relation, SqloError> {
+        self.relations.find(to, related)
+    }
+
+    /// Relation from `from` to its parent accessed by `parent_name`.
+    pub fn get_parent_relation(
+        &self,
+        from: &IdentString,
+        parent_name: &IdentString,
+    ) -> Result<&Relation, SqloError> {
+        let sqlo = self.get(from)?;
+        self.relations
+            .iter()
+            .find(|r| &r.from == from && &r.parent_name(sqlo) == parent_name)
+            .ok_or_else(|| SqloError::new("No relation found", parent_name.span()))
+    }
+
+    /// Many to many relation from `from` accessed by `related`.
+    pub fn get_many_to_many(
+        &self,
+        from: &IdentString,
+        related: &IdentString,
+    ) -> Result<&ManyToMany, SqloError> {
+        self.relations.find_many_to_many(from, related)
+    }
+}
+
+#[cfg(test)]
+impl Sqlos {
+    /// Build Sqlos straight from derive inputs, without the `.sqlo` dir.
+    pub fn from_derive_inputs(inputs: &[syn::DeriveInput]) -> Sqlos {
+        use darling::FromDeriveInput;
+        let entities: Vec<Sqlo> = inputs
+            .iter()
+            .map(|i| {
+                crate::parse::SqloParse::from_derive_input(i)
+                    .unwrap()
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+        let relations = entities.iter().map(Relations::from_sqlo).collect();
+        Sqlos {
+            entities,
+            relations,
+        }
+    }
+}
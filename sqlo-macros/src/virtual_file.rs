@@ -23,7 +23,8 @@ impl VirtualFile {
 
     fn get_table_path() -> (PathBuf, PathBuf) {
         let mut path = PathBuf::new();
-        path.push(env!("CARGO_MANIFEST_DIR"));
+        // crate being compiled, not this one
+        path.push(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo"));
         path.push(".sqlo/");
         if !path.exists() {
             std::fs::create_dir(&path).expect("Unable to create .sqlo/");
@@ -0,0 +1,18 @@
+use std::ops::DerefMut;
+
+use crate::Db;
+
+/// Connection of the executor given to a macro which runs many queries.
+///
+/// A pool gives one of its connections, a transaction gives itself, so every query
+/// runs in the transaction. Used by generated code, where the executor type is only
+/// known once the macro is called.
+#[doc(hidden)]
+pub async fn acquire<'c, A>(
+    executor: A,
+) -> Result<impl DerefMut<Target = <Db as sqlx::Database>::Connection> + 'c, sqlx::Error>
+where
+    A: sqlx::Acquire<'c, Database = Db> + 'c,
+{
+    executor.acquire().await
+}
@@ -0,0 +1,56 @@
+use std::{fmt::Display, future::Future, str::FromStr};
+
+use crate::{Db, EntityMeta, Error, Relation};
+
+/// Common interface of every struct deriving `Sqlo`.
+///
+/// Lets generic code work with any entity:
+///
+/// ```ignore
+/// async fn archive<T: SqloEntity>(pool: &sqlx::Pool<sqlo::Db>, id: T::Pk) -> sqlx::Result<()> {
+///     let row = T::get(pool, id).await?;
+///     // ...
+///     row.save(pool).await?;
+///     Ok(())
+/// }
+/// ```
+pub trait SqloEntity: Sized + Send + Sync + Unpin {
+    /// Owned primary key type, a tuple if primary key is composite.
+    type Pk: Send;
+
+    /// Generated `{Struct}Column` enum.
+    type Column: Copy + FromStr<Err = Error> + Display + Send + Sync + 'static;
+
+    /// Table name.
+    const TABLE: &'static str;
+
+    /// Every column name, in fields order.
+    const COLUMNS: &'static [&'static str];
+
+    /// Primary key column names, many if primary key is composite.
+    const PK_COLUMNS: &'static [&'static str];
+
+    /// Foreign keys held by the entity.
+    const RELATIONS: &'static [Relation];
+
+    /// Description of the table, its columns, primary key and relations.
+    fn sqlo_meta() -> &'static EntityMeta;
+
+    /// Get instance by its primary key.
+    fn get<'c, E: sqlx::Executor<'c, Database = Db>>(
+        pool: E,
+        id: Self::Pk,
+    ) -> impl Future<Output = sqlx::Result<Self>> + Send;
+
+    /// Insert the instance or update it if its primary key already exists.
+    fn save<'c, E: sqlx::Executor<'c, Database = Db>>(
+        &self,
+        pool: E,
+    ) -> impl Future<Output = sqlx::Result<<Db as sqlx::Database>::QueryResult>> + Send;
+
+    /// Delete row by its primary key.
+    fn delete<'c, E: sqlx::Executor<'c, Database = Db>>(
+        pool: E,
+        id: Self::Pk,
+    ) -> impl Future<Output = sqlx::Result<<Db as sqlx::Database>::QueryResult>> + Send;
+}
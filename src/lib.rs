@@ -1,68 +1,26 @@
-mod error;
-#[macro_use]
-mod utils;
-mod database;
-mod field;
-mod macros;
-mod methods;
-mod parse;
-mod produce;
-mod relations;
-mod serdable;
-mod sqlo;
-mod sqlos;
-mod types;
-mod virtual_file;
-
-use crate::parse::SqloParse;
-use crate::sqlo::Sqlo;
-use darling::FromDeriveInput;
-use error::SqloError;
-use macros::Mode;
-use proc_macro2::TokenStream;
-use virtual_file::VirtualFile;
-
-fn process_all(deriveinput: ::syn::DeriveInput) -> syn::Result<TokenStream> {
-    let sqlo: Sqlo = SqloParse::from_derive_input(&deriveinput)?.try_into()?;
-    let vf = VirtualFile::new();
-    vf.update(&sqlo)?;
-    vf.validate(&sqlo)?;
-    if sqlo.parse_only {
-        return Ok(TokenStream::new());
-    }
-    Ok(produce::produce(&sqlo))
-}
+//! Runtime side of sqlo.
+//!
+//! Procedural macros live in `sqlo-macros` and are re-exported here so depending on `sqlo` is enough.
+//! This crate holds what the generated code needs at runtime: traits, errors and helper types.
 
-#[proc_macro_derive(Sqlo, attributes(sqlo))]
-pub fn macro_derive_sqlo(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let deriveinput = syn::parse_macro_input!(input as syn::DeriveInput);
-
-    match process_all(deriveinput) {
-        Ok(ts) => ts.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
-}
-
-#[proc_macro]
-pub fn select(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    match Mode::Select.process(input) {
-        Ok(ts) => ts.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
-}
+mod connection;
+mod entity;
+mod error;
+mod list;
+mod meta;
 
-#[proc_macro]
-pub fn update(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    match Mode::Update.process(input) {
-        Ok(ts) => ts.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
-}
+#[doc(hidden)]
+pub use connection::acquire;
+pub use entity::SqloEntity;
+pub use error::Error;
+pub use list::{json_array, JsonValue};
+pub use meta::{ColumnMeta, EntityMeta, Relation};
+pub use sqlo_macros::{delete, insert, select, update, Sqlo};
 
-#[proc_macro]
-pub fn insert(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    match Mode::Insert.process(input) {
-        Ok(ts) => ts.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
-}
+/// Database selected by the enabled feature.
+#[cfg(feature = "sqlite")]
+pub type Db = sqlx::Sqlite;
+#[cfg(feature = "postgres")]
+pub type Db = sqlx::Postgres;
+#[cfg(feature = "mysql")]
+pub type Db = sqlx::MySql;
@@ -0,0 +1,84 @@
+use std::fmt::Write;
+
+/// Value of a runtime list used with `in` in sqlo macros.
+///
+/// SQLite and MySQL receive the list as a json array, so only scalar values are supported.
+pub trait JsonValue {
+    fn write_json(&self, buf: &mut String);
+}
+
+macro_rules! impl_json_value_as_number {
+    ($($ty:ty),+) => {
+        $(
+        impl JsonValue for $ty {
+            fn write_json(&self, buf: &mut String) {
+                write!(buf, "{}", self).expect("writing to a String can't fail");
+            }
+        }
+        )+
+    };
+}
+
+impl_json_value_as_number!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool);
+
+impl JsonValue for str {
+    fn write_json(&self, buf: &mut String) {
+        buf.push('"');
+        for c in self.chars() {
+            match c {
+                '"' => buf.push_str("\\\""),
+                '\\' => buf.push_str("\\\\"),
+                '\n' => buf.push_str("\\n"),
+                '\r' => buf.push_str("\\r"),
+                '\t' => buf.push_str("\\t"),
+                c if c.is_control() => {
+                    write!(buf, "\\u{:04x}", c as u32).expect("writing to a String can't fail")
+                }
+                c => buf.push(c),
+            }
+        }
+        buf.push('"');
+    }
+}
+
+impl JsonValue for String {
+    fn write_json(&self, buf: &mut String) {
+        self.as_str().write_json(buf)
+    }
+}
+
+impl<T: JsonValue + ?Sized> JsonValue for &T {
+    fn write_json(&self, buf: &mut String) {
+        (**self).write_json(buf)
+    }
+}
+
+/// Json array of `values`, bound in place of a runtime list.
+pub fn json_array<T: JsonValue>(values: &[T]) -> String {
+    let mut buf = String::from("[");
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        value.write_json(&mut buf);
+    }
+    buf.push(']');
+    buf
+}
+
+#[cfg(test)]
+mod test_list {
+    use super::json_array;
+
+    #[test]
+    fn test_json_array() {
+        assert_eq!(json_array::<i64>(&[]), "[]");
+        assert_eq!(json_array(&[1, 2, 3]), "[1,2,3]");
+        assert_eq!(json_array(&[true, false]), "[true,false]");
+        assert_eq!(
+            json_array(&["a", "b\"c\\", "\n\u{1}"]),
+            r#"["a","b\"c\\","\n\u0001"]"#
+        );
+        assert_eq!(json_array(&["a".to_string()]), r#"["a"]"#);
+    }
+}
@@ -1,9 +0,0 @@
-mod insert;
-mod select;
-mod update;
-mod which_macro;
-
-pub use insert::expand_insert;
-pub use select::expand_select;
-pub use update::expand_update;
-pub use which_macro::WhichMacro;
@@ -0,0 +1,58 @@
+/// A foreign key held by an entity, as declared with `#[sqlo(fk = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Relation {
+    /// Field holding the foreign key.
+    pub field: &'static str,
+    /// Column of this field.
+    pub column: &'static str,
+    /// Targeted Sqlo struct.
+    pub to: &'static str,
+    /// Table of the targeted struct.
+    pub to_table: &'static str,
+    /// Identifier used by the target to access this entity.
+    pub related: &'static str,
+}
+
+/// Description of a struct deriving `Sqlo`, see [`SqloEntity::sqlo_meta`](crate::SqloEntity::sqlo_meta).
+#[derive(Debug, PartialEq, Eq)]
+pub struct EntityMeta {
+    /// Sqlo struct name.
+    pub name: &'static str,
+    /// Table name.
+    pub table: &'static str,
+    /// Every column, in fields order.
+    pub columns: &'static [ColumnMeta],
+    /// Primary key column names, many if primary key is composite.
+    pub primary_key: &'static [&'static str],
+    /// Foreign keys held by the entity.
+    pub relations: &'static [Relation],
+}
+
+impl EntityMeta {
+    /// Get a column by its field name.
+    pub fn field(&self, ident: &str) -> Option<&ColumnMeta> {
+        self.columns.iter().find(|c| c.ident == ident)
+    }
+
+    /// Get a column by its column name.
+    pub fn column(&self, column: &str) -> Option<&ColumnMeta> {
+        self.columns.iter().find(|c| c.column == column)
+    }
+}
+
+/// Description of a field of a struct deriving `Sqlo`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColumnMeta {
+    /// Field name.
+    pub ident: &'static str,
+    /// Column name.
+    pub column: &'static str,
+    /// Rust type as written in the struct.
+    pub ty: &'static str,
+    /// Is part of the primary key.
+    pub primary_key: bool,
+    /// Targeted Sqlo struct if foreign key.
+    pub fk: Option<&'static str>,
+    /// Identifier used by the targeted struct to access this entity, if foreign key.
+    pub related: Option<&'static str>,
+}
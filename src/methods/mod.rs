@@ -1,3 +0,0 @@
-pub mod delete;
-pub mod get;
-pub mod save;
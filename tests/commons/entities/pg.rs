@@ -35,7 +35,7 @@ pub struct Maison2 {
 pub struct Adresse {
     pub id: String,
     pub rue: Option<String>,
-    #[sqlo(fk = "Maison", related = "adres")]
+    #[sqlo(fk = "Maison", related = "adres", parent = "lamaison")]
     pub m_id: i32,
 }
 
@@ -71,9 +71,53 @@ pub struct Lit {
 
 #[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
 pub struct SelfRelation {
-    id: i32,
-    name: String,
-    salary: i32,
+    pub id: i32,
+    pub name: String,
+    pub salary: i32,
     #[sqlo(fk = "SelfRelation", related = "manager")]
-    manager_id: Option<i32>,
+    pub manager_id: Option<i32>,
+}
+
+#[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
+pub struct MaisonLit {
+    #[sqlo(primary_key)]
+    pub maison_id: i32,
+    #[sqlo(primary_key)]
+    pub lit_id: i32,
+    pub quantite: i32,
+}
+
+// many to many
+#[derive(sqlo::Sqlo, Debug, PartialEq, Eq)]
+#[sqlo(tablename = "maison")]
+// both target `Lit`, not a duplicate
+#[allow(clippy::duplicated_attributes)]
+#[sqlo(many_to_many(target = "Lit", through = "MaisonLitFk", related = "leslits"))]
+#[sqlo(many_to_many(target = "Lit", through = "MaisonLitFk2", related = "autreslits"))]
+pub struct MaisonM2m {
+    pub id: i32,
+    pub adresse: String,
+    pub taille: i32,
+    pub piscine: Option<bool>,
+}
+
+#[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
+#[sqlo(tablename = "maison_lit")]
+pub struct MaisonLitFk {
+    #[sqlo(primary_key, fk = "MaisonM2m", parent = "maison")]
+    pub maison_id: i32,
+    #[sqlo(primary_key, fk = "Lit")]
+    pub lit_id: i32,
+    pub quantite: i32,
+}
+
+// a second many to many, through the same table
+#[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
+#[sqlo(tablename = "maison_lit")]
+pub struct MaisonLitFk2 {
+    #[sqlo(primary_key, fk = "MaisonM2m", parent = "maison")]
+    pub maison_id: i32,
+    #[sqlo(primary_key, fk = "Lit")]
+    pub lit_id: i32,
+    pub quantite: i32,
 }
@@ -35,7 +35,7 @@ pub struct Maison2 {
 pub struct Adresse {
     pub id: String,
     pub rue: Option<String>,
-    #[sqlo(fk = "Maison", related = "adres")]
+    #[sqlo(fk = "Maison", related = "adres", parent = "lamaison")]
     pub m_id: i64,
 }
 
@@ -71,9 +71,53 @@ pub struct Lit {
 
 #[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
 pub struct SelfRelation {
-    id: i64,
-    name: String,
-    salary: i64,
+    pub id: i64,
+    pub name: String,
+    pub salary: i64,
     #[sqlo(fk = "SelfRelation", related = "manager")]
-    manager_id: Option<i64>,
+    pub manager_id: Option<i64>,
+}
+
+#[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
+pub struct MaisonLit {
+    #[sqlo(primary_key)]
+    pub maison_id: i64,
+    #[sqlo(primary_key)]
+    pub lit_id: i64,
+    pub quantite: i64,
+}
+
+// many to many
+#[derive(sqlo::Sqlo, Debug, PartialEq, Eq)]
+#[sqlo(tablename = "maison")]
+// both target `Lit`, not a duplicate
+#[allow(clippy::duplicated_attributes)]
+#[sqlo(many_to_many(target = "Lit", through = "MaisonLitFk", related = "leslits"))]
+#[sqlo(many_to_many(target = "Lit", through = "MaisonLitFk2", related = "autreslits"))]
+pub struct MaisonM2m {
+    pub id: i64,
+    pub adresse: String,
+    pub taille: i64,
+    pub piscine: Option<bool>,
+}
+
+#[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
+#[sqlo(tablename = "maison_lit")]
+pub struct MaisonLitFk {
+    #[sqlo(primary_key, fk = "MaisonM2m", parent = "maison")]
+    pub maison_id: i64,
+    #[sqlo(primary_key, fk = "Lit")]
+    pub lit_id: i64,
+    pub quantite: i64,
+}
+
+// a second many to many, through the same table
+#[derive(sqlo::Sqlo, PartialEq, Eq, Debug)]
+#[sqlo(tablename = "maison_lit")]
+pub struct MaisonLitFk2 {
+    #[sqlo(primary_key, fk = "MaisonM2m", parent = "maison")]
+    pub maison_id: i64,
+    #[sqlo(primary_key, fk = "Lit")]
+    pub lit_id: i64,
+    pub quantite: i64,
 }
@@ -0,0 +1,63 @@
+use crate::{Maison, MaisonLit, PPool, PieceFk};
+use sqlo::{delete, select};
+
+Test! {delete_where, async fn func(p: PPool) {
+    // simple
+    delete![PieceFk where lg > 5](&p.pool).await.unwrap();
+    assert_eq!(select![*PieceFk](&p.pool).await.unwrap().len(), 5);
+    // every rows
+    delete![PieceFk](&p.pool).await.unwrap();
+    assert!(select![*PieceFk](&p.pool).await.unwrap().is_empty());
+}}
+
+Test! {delete_pk, async fn func(p: PPool) {
+    // brackets
+    delete![Maison[4]](&p.pool).await.unwrap();
+    assert!(Maison::get(&p.pool, 4).await.is_err());
+    assert_eq!(select![*Maison](&p.pool).await.unwrap().len(), 3);
+    // composite
+    delete![MaisonLit[(1, 2)]](&p.pool).await.unwrap();
+    assert!(MaisonLit::get(&p.pool, (1, 2)).await.is_err());
+    assert!(MaisonLit::get(&p.pool, (1, 1)).await.is_ok());
+    // instance
+    let ml = MaisonLit::get(&p.pool, (1, 1)).await.unwrap();
+    delete![MaisonLit(ml)](&p.pool).await.unwrap();
+    assert!(MaisonLit::get(&p.pool, (1, 1)).await.is_err());
+}}
+
+Test! {delete_foreign_key, async fn func(p: PPool) {
+    // relations only
+    delete![Maison[2].lespieces](&p.pool).await.unwrap();
+    assert!(select![*Maison[2].lespieces](&p.pool).await.unwrap().is_empty());
+    assert_eq!(select![*Maison[1].lespieces](&p.pool).await.unwrap().len(), 4);
+    // relations and where
+    delete![Maison[1].lespieces where lg > 2](&p.pool).await.unwrap();
+    assert_eq!(select![*Maison[1].lespieces](&p.pool).await.unwrap().len(), 2);
+    assert_eq!(select![*PieceFk](&p.pool).await.unwrap().len(), 4);
+}}
+
+Test! {delete_where_subquery, async fn func(p: PPool) {
+    // relation fields need a subquery
+    delete![PieceFk where maison_id in {Maison id where taille > 102}](&p.pool).await.unwrap();
+    assert_eq!(select![*PieceFk](&p.pool).await.unwrap().len(), 7);
+}}
+
+#[cfg(not(feature = "mysql"))]
+Test! {delete_returning, async fn func(p: PPool) {
+    // one
+    let res = delete![. Maison[4]](&p.pool).await.unwrap();
+    assert_eq!(res.id, 4);
+    assert_eq!(res.adresse, "adresse4");
+    // many
+    let res = delete![* PieceFk where lg > 7](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    // optional
+    let res = delete![? PieceFk where lg > 7](&p.pool).await.unwrap();
+    assert_eq!(res, None);
+}}
+
+Test! {delete_where_in_runtime_list, async fn func(p: PPool) {
+    let ids = vec![1, 3];
+    delete![PieceFk where maison_id in ::ids](&p.pool).await.unwrap();
+    assert_eq!(select![*PieceFk](&p.pool).await.unwrap().len(), 3);
+}}
@@ -1,4 +1,4 @@
-use crate::{Maison, PPool, PieceFk, PieceFk2};
+use crate::{Maison, MaisonLit, PPool, PieceFk, PieceFk2};
 use sqlo::insert;
 
 Test! {insert_simple, async fn func(p: PPool) {
@@ -56,3 +56,122 @@ Test! {insert_with_create_fn, async fn func(p: PPool) {
    insert![PieceFk2 lg=1, la=2222, maison_id=1](&p.pool).await.unwrap();
    assert_eq!(sqlo::select![.PieceFk2 where la==2222](&p.pool).await.unwrap().lg, 1);
 }}
+
+Test! {insert_many_rows, async fn func(p: PPool) {
+    // list of rows
+    insert![Maison; [(id=5, adresse="lieu5", taille=23, piscine=true), (id=6, adresse="lieu6", taille=24, piscine=None)]](&p.pool).await.unwrap();
+    assert_eq!(Maison::get(&p.pool, 5).await.unwrap(), Maison{id:5, adresse:"lieu5".to_string(), taille: 23, piscine:Some(true)});
+    assert_eq!(Maison::get(&p.pool, 6).await.unwrap(), Maison{id:6, adresse:"lieu6".to_string(), taille: 24, piscine:None});
+
+    // insert_fn is called for every row
+    insert![PieceFk2; [(lg=1, la=3333, maison_id=1), (lg=2, la=3333, maison_id=1)]](&p.pool).await.unwrap();
+    let res = sqlo::select![*PieceFk2 where la==3333 order_by lg](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_ne!(res[0].nb, res[1].nb);
+
+    // slice of instances
+    let maisons = vec![
+        Maison{id:7, adresse:"lieu7".to_string(), taille: 25, piscine:Some(false)},
+        Maison{id:8, adresse:"lieu8".to_string(), taille: 26, piscine:None},
+    ];
+    insert![Maison(&maisons)](&p.pool).await.unwrap();
+    assert_eq!(Maison::get(&p.pool, 7).await.unwrap(), maisons[0]);
+    assert_eq!(Maison::get(&p.pool, 8).await.unwrap(), maisons[1]);
+
+    // empty slice does nothing
+    let empty: Vec<Maison> = vec![];
+    insert![Maison(&empty)](&p.pool).await.unwrap();
+
+    // insert_fn gives the primary key of every instance
+    let pieces = vec![
+        PieceFk2{nb: uuid::Uuid::nil(), lg: 1, la: 4444, maison_id: 1},
+        PieceFk2{nb: uuid::Uuid::nil(), lg: 2, la: 4444, maison_id: 1},
+    ];
+    insert![PieceFk2(&pieces)](&p.pool).await.unwrap();
+    let res = sqlo::select![*PieceFk2 where la==4444 order_by lg](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_ne!(res[0].nb, res[1].nb);
+    assert_ne!(res[0].nb, uuid::Uuid::nil());
+}}
+
+Test! {insert_many_instances_by_chunks, async fn func(p: PPool) {
+    // more bind parameters than accepted by a single query
+    let maisons = (100..1200)
+        .map(|id| Maison{id, adresse: format!("lieu{id}"), taille: id, piscine: None})
+        .collect::<Vec<_>>();
+    let res = insert![Maison(&maisons)](&p.pool).await.unwrap();
+    assert_eq!(res.rows_affected(), 1100);
+    let count = sqlo::select![.Maison count(id) as "total!:i64" where id >= 100](&p.pool).await.unwrap();
+    assert_eq!(count.total, 1100);
+
+    #[cfg(not(feature = "mysql"))]
+    {
+        let maisons = (2000..3100)
+            .map(|id| Maison{id, adresse: format!("lieu{id}"), taille: id, piscine: None})
+            .collect::<Vec<_>>();
+        let res = insert![* Maison(&maisons)](&p.pool).await.unwrap();
+        assert_eq!(res, maisons);
+    }
+
+    // every chunk in the same transaction
+    let maisons = (4000..5100)
+        .map(|id| Maison{id, adresse: format!("lieu{id}"), taille: id, piscine: None})
+        .collect::<Vec<_>>();
+    let mut tx = p.pool.begin().await.unwrap();
+    let res = insert![Maison(&maisons)](&mut *tx).await.unwrap();
+    assert_eq!(res.rows_affected(), 1100);
+    tx.rollback().await.unwrap();
+    let count = sqlo::select![.Maison count(id) as "total!:i64" where id >= 4000](&p.pool).await.unwrap();
+    assert_eq!(count.total, 0);
+}}
+
+#[cfg(not(feature = "mysql"))]
+Test! {insert_many_rows_returning, async fn func(p: PPool) {
+    // list of rows
+    let res = insert![* Maison; [(id=5, adresse="lieu5", taille=23), (id=6, adresse="lieu6", taille=24)]](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1], Maison{id:6, adresse:"lieu6".to_string(), taille: 24, piscine:None});
+
+    // slice of instances
+    let maisons = vec![
+        Maison{id:7, adresse:"lieu7".to_string(), taille: 25, piscine:Some(true)},
+        Maison{id:8, adresse:"lieu8".to_string(), taille: 26, piscine:None},
+    ];
+    let res = insert![* Maison(&maisons)](&p.pool).await.unwrap();
+    assert_eq!(res, maisons);
+}}
+
+Test! {insert_on_conflict, async fn func(p: PPool) {
+    // do nothing
+    insert![Maison id=1, adresse="autre", taille=1 on_conflict(id) nothing](&p.pool).await.unwrap();
+    let res = Maison::get(&p.pool, 1).await.unwrap();
+    assert_eq!((res.adresse.as_str(), res.taille), ("adresse1", 101));
+
+    // update with the proposed value
+    insert![Maison id=1, adresse="autre", taille=1 on_conflict(id) update adresse=excluded.adresse](&p.pool).await.unwrap();
+    let res = Maison::get(&p.pool, 1).await.unwrap();
+    assert_eq!((res.adresse.as_str(), res.taille), ("autre", 101));
+
+    // no conflict: simple insert
+    insert![Maison id=5, adresse="lieu5", taille=5 on_conflict(id) update adresse=excluded.adresse](&p.pool).await.unwrap();
+    assert_eq!(Maison::get(&p.pool, 5).await.unwrap().adresse, "lieu5");
+
+    // many columns target, existing value and rust value
+    let extra = 10;
+    insert![MaisonLit maison_id=1, lit_id=1, quantite=5 on_conflict(maison_id, lit_id) update quantite=quantite + excluded.quantite + ::extra](&p.pool).await.unwrap();
+    assert_eq!(MaisonLit::get(&p.pool, (1, 1)).await.unwrap().quantite, 17);
+
+    // many rows
+    insert![Maison; [(id=2, adresse="deux", taille=2), (id=6, adresse="six", taille=6)] on_conflict(id) update taille=excluded.taille](&p.pool).await.unwrap();
+    assert_eq!(Maison::get(&p.pool, 2).await.unwrap().taille, 2);
+    assert_eq!(Maison::get(&p.pool, 6).await.unwrap().taille, 6);
+
+    // instances
+    let maisons = vec![
+        Maison{id:3, adresse:"trois".to_string(), taille: 3, piscine:None},
+        Maison{id:7, adresse:"sept".to_string(), taille: 7, piscine:None},
+    ];
+    insert![Maison(&maisons) on_conflict(id) update taille=excluded.taille + ::extra](&p.pool).await.unwrap();
+    assert_eq!(Maison::get(&p.pool, 3).await.unwrap().taille, 13);
+    assert_eq!(Maison::get(&p.pool, 7).await.unwrap().taille, 7);
+}}
@@ -1,4 +1,4 @@
-use crate::{Adresse, Maison, Maison2, PPool, WithAttrs};
+use crate::{Adresse, Maison, Maison2, MaisonLit, PPool, PieceFk, SelfRelation, WithAttrs};
 Test! {pseudo_hidden_methods, async fn func(p: PPool) {
     //test tablname
     //test utils as instance
@@ -79,3 +79,159 @@ Test! {delete, async fn func(p: PPool) {
     Maison::delete(&p.pool, m.id).await.unwrap();
     assert!(Maison::get(&p.pool, m.id).await.is_err());
 }}
+
+Test! {composite_primary_key, async fn func(p: PPool) {
+    // get and pk
+    let ml = MaisonLit::get(&p.pool, (1, 2)).await.unwrap();
+    assert_eq!(ml, MaisonLit{maison_id: 1, lit_id: 2, quantite: 1});
+    assert_eq!(ml.pk(), (&1, &2));
+
+    // save: insert then update
+    let mut sn = MaisonLit{maison_id: 3, lit_id: 1, quantite: 7};
+    sn.save(&p.pool).await.unwrap();
+    assert_eq!(MaisonLit::get(&p.pool, (3, 1)).await.unwrap(), sn);
+    sn.quantite = 8;
+    sn.save(&p.pool).await.unwrap();
+    assert_eq!(MaisonLit::get(&p.pool, (3, 1)).await.unwrap().quantite, 8);
+    // other rows sharing part of the pk are untouched
+    assert_eq!(MaisonLit::get(&p.pool, (1, 1)).await.unwrap().quantite, 2);
+
+    // delete by pk
+    MaisonLit::delete(&p.pool, (1, 1)).await.unwrap();
+    assert!(MaisonLit::get(&p.pool, (1, 1)).await.is_err());
+    assert!(MaisonLit::get(&p.pool, (1, 2)).await.is_ok());
+
+    // remove by instance
+    sn.remove(&p.pool).await.unwrap();
+    assert!(MaisonLit::get(&p.pool, (3, 1)).await.is_err());
+}}
+
+Test! {related_methods, async fn func(p: PPool) {
+    // from fk to its target
+    let piece = PieceFk::get(&p.pool, &uu4!(3)).await.unwrap();
+    assert_eq!(piece.maison(&p.pool).await.unwrap(), Maison::get(&p.pool, 2).await.unwrap());
+    let adresse = Adresse::get(&p.pool, "1").await.unwrap();
+    assert_eq!(adresse.lamaison(&p.pool).await.unwrap().id, 1);
+
+    // from target to every fk
+    let maison = Maison::get(&p.pool, 1).await.unwrap();
+    let pieces = maison.lespieces(&p.pool).await.unwrap();
+    assert_eq!(pieces.len(), 4);
+    assert!(pieces.iter().all(|p| p.maison_id == 1));
+    // default related name
+    assert_eq!(maison.piece_fk2(&p.pool).await.unwrap().len(), 4);
+    assert_eq!(maison.adres(&p.pool).await.unwrap(), vec![adresse]);
+
+    // self relation with optional fk
+    let axel = SelfRelation::get(&p.pool, 1).await.unwrap();
+    let papa = axel.manager(&p.pool).await.unwrap().unwrap();
+    assert_eq!(papa, SelfRelation::get(&p.pool, 3).await.unwrap());
+    assert!(papa.manager(&p.pool).await.unwrap().is_none());
+}}
+
+Test! {load_for_parents, async fn func(p: PPool) {
+    let maisons = vec![
+        Maison::get(&p.pool, 1).await.unwrap(),
+        Maison::get(&p.pool, 2).await.unwrap(),
+        Maison::get(&p.pool, 4).await.unwrap(),
+    ];
+    let pieces = PieceFk::load_for_maisons(&p.pool, &maisons).await.unwrap();
+    assert_eq!(pieces.len(), 3);
+    assert_eq!(pieces[&1].len(), 4);
+    assert!(pieces[&2].iter().all(|p| p.maison_id == 2));
+    assert!(pieces[&4].is_empty());
+    // parent name
+    let adresses = Adresse::load_for_lamaisons(&p.pool, &maisons[..1]).await.unwrap();
+    assert_eq!(adresses[&1], vec![Adresse::get(&p.pool, "1").await.unwrap()]);
+    // no parent, no query
+    assert!(PieceFk::load_for_maisons(&p.pool, &[]).await.unwrap().is_empty());
+    // self relation with optional fk
+    let papa = SelfRelation::get(&p.pool, 3).await.unwrap();
+    let employees = SelfRelation::load_for_managers(&p.pool, &[papa]).await.unwrap();
+    assert_eq!(employees[&3], vec![SelfRelation::get(&p.pool, 1).await.unwrap()]);
+    // more parents than bind parameters accepted by a single query
+    let maisons = (100..1200)
+        .map(|id| Maison{id, adresse: format!("lieu{id}"), taille: id, piscine: None})
+        .collect::<Vec<_>>();
+    sqlo::insert![Maison(&maisons)](&p.pool).await.unwrap();
+    sqlo::insert![PieceFk lg=1, la=1, maison_id=1150](&p.pool).await.unwrap();
+    let pieces = PieceFk::load_for_maisons(&p.pool, &maisons).await.unwrap();
+    assert_eq!(pieces.len(), 1100);
+    assert_eq!(pieces[&1150].len(), 1);
+    assert_eq!(pieces.values().map(Vec::len).sum::<usize>(), 1);
+}}
+
+Test! {sqlo_entity_trait, async fn func(p: PPool) {
+    use sqlo::SqloEntity;
+    // generic code written once for every entity
+    async fn take<T: SqloEntity>(pool: &sqlx::Pool<sqlo::Db>, id: T::Pk) -> sqlx::Result<T> {
+        let row = T::get(pool, id).await?;
+        row.save(pool).await?;
+        Ok(row)
+    }
+    async fn remove<T: SqloEntity>(pool: &sqlx::Pool<sqlo::Db>, id: T::Pk) -> u64 {
+        T::delete(pool, id).await.unwrap().rows_affected()
+    }
+    // simple pk
+    assert_eq!(take::<Maison>(&p.pool, 1).await.unwrap().adresse, "adresse1");
+    // String pk
+    assert_eq!(take::<Adresse>(&p.pool, "2".to_string()).await.unwrap().m_id, 2);
+    // uuid pk
+    assert_eq!(take::<PieceFk>(&p.pool, uu4!(3)).await.unwrap().lg, 3);
+    // composite pk
+    assert_eq!(take::<MaisonLit>(&p.pool, (1, 2)).await.unwrap().quantite, 1);
+    assert_eq!(remove::<MaisonLit>(&p.pool, (1, 2)).await, 1);
+    assert_eq!(remove::<Adresse>(&p.pool, "2".to_string()).await, 1);
+    assert!(Adresse::get(&p.pool, "2").await.is_err());
+
+    // metadata
+    assert_eq!(Maison::TABLE, "maison");
+    assert_eq!(Maison::COLUMNS, &["id", "adresse", "taille", "piscine"]);
+    assert_eq!(Maison::PK_COLUMNS, &["id"]);
+    assert!(Maison::RELATIONS.is_empty());
+    assert_eq!(WithAttrs::COLUMNS, &["nb", "lg", "la", "maison_id"]);
+    assert_eq!(MaisonLit::PK_COLUMNS, &["maison_id", "lit_id"]);
+    assert_eq!(
+        Adresse::RELATIONS,
+        &[sqlo::Relation {
+            field: "m_id",
+            column: "m_id",
+            to: "Maison",
+            to_table: "maison",
+            related: "adres"
+        }]
+    );
+}}
+
+Test! {sqlo_meta, async fn func(_p: PPool) {
+    use sqlo::{ColumnMeta, SqloEntity};
+    let meta = WithAttrs::sqlo_meta();
+    assert_eq!(meta.name, "WithAttrs");
+    assert_eq!(meta.table, "piece");
+    assert_eq!(meta.primary_key, &["nb"]);
+    assert_eq!(meta.columns.len(), 4);
+    assert_eq!(
+        meta.field("lglg").unwrap(),
+        &ColumnMeta {
+            ident: "lglg",
+            column: "lg",
+            ty: "i32",
+            primary_key: false,
+            fk: None,
+            related: None
+        }
+    );
+    assert_eq!(meta.column("nb").unwrap().ty, "uuid::Uuid");
+    assert!(meta.column("nb").unwrap().primary_key);
+    assert!(meta.column("lglg").is_none());
+    // foreign keys
+    let meta = Adresse::sqlo_meta();
+    assert_eq!(meta.field("rue").unwrap().ty, "Option<String>");
+    let m_id = meta.field("m_id").unwrap();
+    assert_eq!(m_id.fk, Some("Maison"));
+    assert_eq!(m_id.related, Some("adres"));
+    assert_eq!(meta.relations, Adresse::RELATIONS);
+    // composite primary key
+    assert_eq!(MaisonLit::sqlo_meta().primary_key, &["maison_id", "lit_id"]);
+    assert_eq!(PieceFk::sqlo_meta().field("maison_id").unwrap().related, Some("lespieces"));
+}}
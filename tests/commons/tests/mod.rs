@@ -1,8 +1,10 @@
+mod delete;
 mod insert;
 mod methods;
 mod sqlo_select;
 mod update;
 
+pub use delete::*;
 pub use insert::*;
 pub use methods::*;
 pub use sqlo_select::*;
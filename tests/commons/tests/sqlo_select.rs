@@ -1,5 +1,8 @@
-use crate::{Lit, Maison, PPool, PieceFk, PieceFk2};
-use sqlo::select;
+use crate::{
+    Adresse, Lit, Maison, MaisonLit, MaisonLitFk, MaisonM2m, PPool, PieceFk, PieceFk2,
+    SelfRelation, WithAttrs,
+};
+use sqlo::{select, update};
 
 Test! {select_with_pk, async fn func(p: PPool) {
     // --------------------- select easy -----------------------//
@@ -14,6 +17,12 @@ Test! {select_with_pk, async fn func(p: PPool) {
     // only pk
     let res2 = select![.Maison[1]](&p.pool).await.unwrap();
     assert_eq!(res, res2);
+    // composite pk
+    let res = select![.MaisonLit[(1, 2)]](&p.pool).await.unwrap();
+    assert_eq!(res.quantite, 1);
+    let (m, l) = (2, 3);
+    let res = select![.MaisonLit[(m, l)]](&p.pool).await.unwrap();
+    assert_eq!(res.quantite, 4);
 }}
 
 Test! {select_with_attribute, async fn func(p: PPool) {
@@ -89,6 +98,35 @@ Test! {select_test_where_null, async fn func(p: PPool) {
     nb_result!(p,Maison, piscine != None, 0);
 }}
 
+Test! {select_test_where_optional, async fn func(p: PPool) {
+    // predicate is skipped when None
+    let mut min_la = None;
+    let mut maison = None;
+    let res = select![*PieceFk where la > ?::min_la && maison_id == ?::maison](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 9);
+    min_la = Some(30);
+    let res = select![*PieceFk where la > ?::min_la && maison_id == ?::maison](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 6);
+    maison = Some(2);
+    let res = select![*PieceFk where la > ?::min_la && maison_id == ?::maison](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    let res = select![*PieceFk where maison_id == ?::maison || la == 10](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 4);
+    // like
+    let pattern: Option<String> = None;
+    let res = select![*Maison where adresse # ?::pattern](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 4);
+    let pattern = Some("%3".to_string());
+    let res = select![*Maison where adresse # ?::pattern](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 1);
+    // arithmetic on the left operand
+    let min_taille = Some(103);
+    let res = select![*Maison where taille + 1 > ?::min_taille](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    let res = select![*Maison where taille + 1 > ?::min_taille && id != 4](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 1);
+}}
+
 Test! {select_test_where_between, async fn func(p: PPool) {
     nb_result!(p,PieceFk, la <= 30 && la < 60, 3);
     nb_result!(p,PieceFk, la <= 30 && la > 30 || la == 50, 1);
@@ -156,6 +194,68 @@ Test! {select_test_wherein, async fn func(p: PPool) {
     assert_eq!(res.len(), 7);
 }}
 
+Test! {select_test_where_in_runtime_list, async fn func(p: PPool) {
+    let ids = vec![1, 3];
+    let res = select![*PieceFk where maison_id in ::ids](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 6);
+    // followed by other conditions
+    let res = select![*PieceFk where maison_id in ::ids && lg > 5](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 3);
+    // without `::`, with other arguments
+    let min = 30;
+    let res = select![*PieceFk where la > ::min && maison_id in ids](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 4);
+    // slice and custom columns
+    let ids = &ids[..1];
+    let res = select![*PieceFk maison_id, count(nb) as "total!:i64" where maison_id in ::ids group_by maison_id](&p.pool).await.unwrap();
+    assert_eq!(res[0].total, 4);
+    // empty
+    let ids: Vec<i32> = vec![];
+    assert!(select![*Maison where id in ::ids](&p.pool).await.unwrap().is_empty());
+    // strings
+    let ids = vec!["1".to_string(), "3".to_string()];
+    let res = select![*Adresse where id in ::ids order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), vec!["1", "3"]);
+}}
+
+Test! {select_test_where_range, async fn func(p: PPool) {
+    assert_eq!(select![*PieceFk where lg in 2..=4](&p.pool).await.unwrap().len(), 3);
+    assert_eq!(select![*PieceFk where lg in 2..4](&p.pool).await.unwrap().len(), 2);
+    assert_eq!(select![*PieceFk where lg in 8..](&p.pool).await.unwrap().len(), 2);
+    assert_eq!(select![*PieceFk where lg in ..3](&p.pool).await.unwrap().len(), 2);
+    assert_eq!(select![*PieceFk where lg in ..=3](&p.pool).await.unwrap().len(), 3);
+    // arguments and following conditions
+    let (lo, hi) = (2, 4);
+    let res = select![*PieceFk where lg in ::lo..=::hi && maison_id == 1](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 1);
+}}
+
+Test! {select_test_where_negated_and_null_safe, async fn func(p: PPool) {
+    // not in
+    assert_eq!(select![*Maison where id !in (1,3)](&p.pool).await.unwrap().len(), 2);
+    let ids = vec![1, 3];
+    let res = select![*Maison where id !in ::ids order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|m| m.id).collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(select![*Maison where id !in 2..=3](&p.pool).await.unwrap().len(), 2);
+    // not like, ilike
+    assert_eq!(select![*Maison where adresse !# "adr%"](&p.pool).await.unwrap().len(), 1);
+    assert_eq!(select![*Maison where adresse #* "ADR%"](&p.pool).await.unwrap().len(), 3);
+    assert_eq!(select![*Maison where adresse !#* "ADR%"](&p.pool).await.unwrap().len(), 1);
+    // null safe equality
+    let mut piscine = None;
+    assert_eq!(select![*Maison where piscine <=> ::piscine](&p.pool).await.unwrap().len(), 4);
+    piscine = Some(true);
+    assert_eq!(select![*Maison where piscine <=> ::piscine](&p.pool).await.unwrap().len(), 0);
+    assert_eq!(select![*Maison where piscine !<=> ::piscine](&p.pool).await.unwrap().len(), 4);
+}}
+
+// sqlite has no builtin regexp function
+#[cfg(not(feature = "sqlite"))]
+Test! {select_test_where_regex, async fn func(p: PPool) {
+    assert_eq!(select![*Maison where adresse ~ "^adr"](&p.pool).await.unwrap().len(), 3);
+    assert_eq!(select![*Maison where adresse !~ "^adr"](&p.pool).await.unwrap().len(), 1);
+}}
+
 Test! {select_test_where_like, async fn func(p: PPool) {
     let res = select![*Maison where adresse # "adr%"](&p.pool).await.unwrap();
     assert_eq!(res.len(), 3);
@@ -210,6 +310,95 @@ Test! {select_test_where_foreign_key, async fn func(p: PPool) {
     assert_eq!(res.len(), 1);
 }}
 
+Test! {select_reverse_join, async fn func(p: PPool) {
+    // parent name is the snake_case fk struct
+    let res = select![*PieceFk where maison.taille > 102](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    // as column
+    let res = select![*PieceFk la, maison.adresse as adr where maison.id == 2 order_by la](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[0].la, 30);
+    assert_eq!(res[0].adr, "   adresse2    ");
+    // parent name given by attribute
+    let res = select![*Adresse id, lamaison.taille as taille where lamaison.taille >= 102 order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].taille, 103);
+}}
+
+Test! {select_many_to_many, async fn func(p: PPool) {
+    // in where: joined, a maison matching many lits would be repeated
+    let res = select![*MaisonM2m where leslits.surface > 200 order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].id, 1);
+    assert_eq!(res[1].id, 2);
+    // as column
+    let res = select![*MaisonM2m id, leslits.surface as surface where id == 1 order_by surface](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].surface, 100);
+    assert_eq!(res[1].surface, 234);
+    // related
+    let res = select![*MaisonM2m[1].leslits order_by id](&p.pool).await.unwrap();
+    assert_eq!(res, vec![Lit{id:1, surface:234}, Lit{id:2, surface:100}]);
+    let maison = MaisonM2m::get(&p.pool, 2).await.unwrap();
+    let res = select![*MaisonM2m[maison.id].leslits where surface > 300](&p.pool).await.unwrap();
+    assert_eq!(res, vec![Lit{id:3, surface:450}]);
+    // many of them, each with its own join
+    let res = select![*MaisonM2m where leslits.surface > 200 && autreslits.surface < 200](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|m| m.id).collect::<Vec<_>>(), vec![1]);
+    let res = select![*MaisonM2m[2].autreslits](&p.pool).await.unwrap();
+    assert_eq!(res, vec![Lit{id:3, surface:450}]);
+}}
+
+Test! {select_multi_hop_join, async fn func(p: PPool) {
+    // parent then children
+    let res = select![*PieceFk where maison.adres.rue == "adresse1"](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 4);
+    // as column and order_by
+    let res = select![*PieceFk la, maison.adres.id as adr where maison.taille > 102 order_by maison.adres.id, la](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].la, 40);
+    assert_eq!(res[0].adr, "3");
+    // parent then many to many
+    let res = select![*MaisonLitFk where maison.leslits.surface == 234](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    // each hop with its own join
+    let res = select![*SelfRelation name!, manager=.manager=.name as boss? order_by name](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[0].boss, None);
+    assert_eq!(res[1].boss, Some("papa".to_string()));
+}}
+
+Test! {select_include, async fn func(p: PPool) {
+    let res = select![*Maison include lespieces where taille > 101 order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[0].0.id, 2);
+    assert_eq!(res[0].1.len(), 3);
+    assert!(res[0].1.iter().all(|piece| piece.maison_id == 2));
+    assert_eq!(res[1].1.len(), 2);
+    assert!(res[2].1.is_empty());
+    // one
+    let (maison, adresses) = select![.Maison include adres where id == 1](&p.pool).await.unwrap();
+    assert_eq!(maison.id, 1);
+    assert_eq!(adresses, vec![Adresse{id: "1".to_string(), rue: Some("adresse1".to_string()), m_id: 1}]);
+    // optional
+    let res = select![?Maison include lespieces where id == 99](&p.pool).await.unwrap();
+    assert!(res.is_none());
+    // no parent
+    let res = select![*Maison include lespieces where id > 99](&p.pool).await.unwrap();
+    assert!(res.is_empty());
+    // more parents than bind parameters accepted by a single query
+    let maisons = (100..1200)
+        .map(|id| Maison{id, adresse: format!("lieu{id}"), taille: id, piscine: None})
+        .collect::<Vec<_>>();
+    sqlo::insert![Maison(&maisons)](&p.pool).await.unwrap();
+    sqlo::insert![PieceFk lg=1, la=1, maison_id=1150](&p.pool).await.unwrap();
+    let res = select![*Maison include lespieces where id >= 100 order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 1100);
+    assert_eq!(res[1050].0.id, 1150);
+    assert_eq!(res[1050].1.len(), 1);
+    assert_eq!(res.iter().map(|(_, pieces)| pieces.len()).sum::<usize>(), 1);
+}}
+
 Test! {select_test_where_call, async fn func(p:PPool){
     // simple
     let res = select![.Maison where trim(adresse) == "adresse2"](&p.pool).await.unwrap();
@@ -222,6 +411,139 @@ Test! {select_test_where_call, async fn func(p:PPool){
     assert_eq!(res.adresse, "   adresse2    ");
 }}
 
+Test! {select_test_where_method, async fn func(p:PPool){
+    let res = select![.Maison where adresse.trim() == "adresse2"](&p.pool).await.unwrap();
+    assert_eq!(res.adresse, "   adresse2    ");
+    // chained, in column
+    let res = select![.Maison adresse.trim().to_uppercase() as "adresse?:String" where id == 2](&p.pool).await.unwrap();
+    assert_eq!(res.adresse, Some("ADRESSE2".into()));
+    // like
+    assert_eq!(select![*Maison where adresse.starts_with("adr")](&p.pool).await.unwrap().len(), 3);
+    assert_eq!(select![*Maison where adresse.ends_with("3")](&p.pool).await.unwrap().len(), 1);
+    assert_eq!(select![*Maison where adresse.contains("dresse")](&p.pool).await.unwrap().len(), 4);
+    let end = "4";
+    assert_eq!(select![*Maison where adresse.ends_with(::end)](&p.pool).await.unwrap().len(), 1);
+    // `%` and `_` are not wildcards
+    assert!(select![*Maison where adresse.starts_with("adr_")](&p.pool).await.unwrap().is_empty());
+    let start = "adr%";
+    assert!(select![*Maison where adresse.starts_with(::start)](&p.pool).await.unwrap().is_empty());
+    sqlo::update![Maison[4] adresse = "50%_off!"](&p.pool).await.unwrap();
+    assert_eq!(select![*Maison where adresse.contains("%_off!")](&p.pool).await.unwrap().len(), 1);
+    let end = "_off!";
+    assert_eq!(select![*Maison where adresse.ends_with(::end)](&p.pool).await.unwrap().len(), 1);
+    // case sensitive like the rust methods
+    assert!(select![*Maison where adresse.starts_with("ADR")](&p.pool).await.unwrap().is_empty());
+    let middle = "DRESSE";
+    assert!(select![*Maison where adresse.contains(::middle)](&p.pool).await.unwrap().is_empty());
+    // nor are other wildcards
+    sqlo::update![Maison[3] adresse = "a[*?]b"](&p.pool).await.unwrap();
+    assert_eq!(select![*Maison where adresse.contains("[*?]")](&p.pool).await.unwrap().len(), 1);
+    assert!(select![*Maison where adresse.starts_with("a*")](&p.pool).await.unwrap().is_empty());
+    let end = "?]b";
+    assert_eq!(select![*Maison where adresse.ends_with(::end)](&p.pool).await.unwrap().len(), 1);
+    // with fk
+    assert_eq!(select![*Maison where adres.rue.trim().len() == 8](&p.pool).await.unwrap().len(), 3);
+    // numbers and null
+    assert_eq!(select![*Maison where (-taille).abs() == 101](&p.pool).await.unwrap().len(), 1);
+    assert_eq!(select![*Maison where taille.round() == 102](&p.pool).await.unwrap().len(), 1);
+    assert_eq!(select![*Maison where piscine.unwrap_or(false) == false](&p.pool).await.unwrap().len(), 4);
+}}
+
+Test! {select_window_function, async fn func(p:PPool){
+    let res = select![*PieceFk lg, row_number() over(partition_by maison_id order_by -lg) as "rn!:i64" where maison_id == 1 order_by lg](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.lg).collect::<Vec<_>>(), vec![1, 2, 6, 9]);
+    assert_eq!(res.iter().map(|r| r.rn).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+    // partition only
+    let res = select![*PieceFk lg, count(*) over(partition_by maison_id) as "n!:i64" order_by lg](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.n).collect::<Vec<_>>(), vec![4, 4, 3, 2, 3, 4, 2, 3, 4]);
+    // related field in window
+    let res = select![*Maison id, row_number() over(order_by -adres.rue) as "rn!:i64" order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.rn).collect::<Vec<_>>(), vec![2, 3, 1]);
+}}
+
+Test! {select_with_cte, async fn func(p:PPool){
+    // aliases of the cte are fields of the main query
+    let res = select![with big = {Maison id, taille as t where taille > 101} *big id, t where t < 104 order_by -id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3, 2]);
+    assert_eq!(res.iter().map(|r| r.t).collect::<Vec<_>>(), vec![103, 102]);
+    // cte in a subquery, arguments of cte and main query
+    let (min, id) = (101, 3);
+    let res = select![with big = {Maison id where taille > min} *Maison where id in {big id} && id != ::id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 4]);
+    // many ctes, whole rows with a custom struct
+    let res = select![with big = {Maison where taille > 101}, bigger = {big where taille > 102} *Maison, bigger order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3, 4]);
+    assert_eq!(res[0].adresse, "adresse3");
+    // computed column
+    let res = select![with n = {PieceFk maison_id, count(*) as nb group_by maison_id} .n max(nb) as "m!:i64"](&p.pool).await.unwrap();
+    assert_eq!(res.m, 4);
+    // cte inside a subquery or a cte, only known there
+    let res = select![*Maison where id in {with big = {Maison id where taille > 102} big id} order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3, 4]);
+    let res = select![with big = {with bigger = {Maison id where taille > 102} bigger id} *big id order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3, 4]);
+}}
+
+Test! {select_recursive, async fn func(p:PPool){
+    // descendants with the related name
+    let res = select![*SelfRelation[3].manager recursive depth 10 order_by id](&p.pool).await.unwrap();
+    assert_eq!(res, vec![SelfRelation::get(&p.pool, 1).await.unwrap(), SelfRelation::get(&p.pool, 2).await.unwrap()]);
+    // limited depth
+    let max = 1;
+    let res = select![*SelfRelation[3].manager recursive depth max name!](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["axel"]);
+    // ancestors with the parent name, depth as column
+    let res = select![*SelfRelation[2].self_relation recursive depth 10 name! where sqlo_depth >= 1 order_by -sqlo_depth](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["papa", "axel"]);
+    let res = select![.SelfRelation[2].self_relation recursive depth 10 count(*) as "n!:i64" where sqlo_depth == 2](&p.pool).await.unwrap();
+    assert_eq!(res.n, 1);
+    // a cycle stops at the max depth: 3 -> 1 -> 2 -> 3 -> 1
+    let mut tx = p.pool.begin().await.unwrap();
+    update![SelfRelation[3] manager_id = 2](&mut *tx).await.unwrap();
+    let res = select![*SelfRelation[3].manager recursive depth 4 order_by sqlo_depth](&mut *tx).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1, 2, 3, 1]);
+    tx.rollback().await.unwrap();
+}}
+
+Test! {select_union, async fn func(p:PPool){
+    // whole rows, ordered by the combined query
+    let res = select![*Maison where taille > 103 union Maison where id == 1 order_by -id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![4, 1]);
+    // union all keeps duplicates, arguments on both sides
+    let (max, id) = (3, 2);
+    let res = select![*Maison id where id < max union all Maison id where id == ::id order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1, 2, 2]);
+    // duplicates inside each side too
+    let res = select![*PieceFk maison_id where lg < 5 union all PieceFk maison_id where lg > 7 order_by maison_id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.maison_id).collect::<Vec<_>>(), vec![1, 1, 1, 2, 2, 3]);
+    // intersect, except and limit
+    let res = select![*Maison id where taille > 101 intersect Maison id where taille < 104 order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 3]);
+    let res = select![*Maison id where taille > 101 except Maison id where id == 3 order_by -id limit 1](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![4]);
+    // same aliases from different structs
+    let res = select![*PieceFk lg as "n!" where lg < 2 union Maison id as "n!" where id == 4 order_by n](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.n).collect::<Vec<_>>(), vec![1, 4]);
+}}
+
+Test! {select_derived_table, async fn func(p:PPool){
+    // aggregate of an aggregate
+    let res = select![. {Maison id, count(lespieces.nb) as n group_by id} as t max(t.n) as "m!:i64", min(n) as "mi!:i64"](&p.pool).await.unwrap();
+    assert_eq!((res.m, res.mi), (4, 2));
+    // aliases of the subquery are fields, arguments inside and outside
+    let (min, max) = (101, 3);
+    let res = select![* {Maison id, taille as t where taille > min} as big id where id < max order_by -id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2]);
+    // whole rows with a custom struct
+    let res = select![*Maison, {Maison where taille > 102} as big order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3, 4]);
+    // derived table inside a derived table or a cte
+    let res = select![* { {Maison id, taille where taille > 101} as a id, taille where taille < 104 } as t id order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 3]);
+    let res = select![with t = { {Maison id, taille where taille > 101} as a id where taille < 104 } *t id order_by id](&p.pool).await.unwrap();
+    assert_eq!(res.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 3]);
+}}
+
 Test! {select_cutoms_fields, async fn func(p: PPool) {
   // field
   let  res = select![*Maison id](&p.pool).await.unwrap();
@@ -606,6 +928,44 @@ Test! {select_sub_select_exists, async fn func(p:PPool){
     assert_eq!(res.len(), 4);
 }}
 
+Test! {select_any_all, async fn func(p:PPool){
+    // subquery
+    let res = select![*PieceFk where lg > all {Maison id}](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 5);
+    let res = select![*PieceFk where lg == any {Maison id}](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 4);
+    let res = select![*PieceFk where lg != all {Maison id}](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 5);
+    let res = select![*PieceFk where lg < any {Maison id where id < 3}](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 1);
+    // runtime list
+    let ids = vec![1, 3];
+    let res = select![*Maison where id == any(::ids)](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    let res = select![*Maison where id != all(ids)](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+    #[cfg(not(feature = "mysql"))]
+    {
+        let res = select![*Maison where id > all(::ids)](&p.pool).await.unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, 4);
+    }
+    // comparing NULL is unknown: the row is dropped
+    sqlo::update![Maison[1] piscine = true](&p.pool).await.unwrap();
+    sqlo::update![Maison[2] piscine = false](&p.pool).await.unwrap();
+    let res = select![*Maison where piscine >= all {Maison piscine where id < 3}](&p.pool).await.unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, 1);
+    let res = select![*Maison where piscine >= all {Maison piscine}](&p.pool).await.unwrap();
+    assert!(res.is_empty());
+    #[cfg(not(feature = "mysql"))]
+    {
+        let flags = vec![false];
+        let res = select![*Maison where piscine >= all(::flags) order_by id](&p.pool).await.unwrap();
+        assert_eq!(res.iter().map(|m| m.id).collect::<Vec<_>>(), vec![1, 2]);
+    }
+}}
+
 Test! {select_case, async fn func(p:PPool){
     //simple with case
     let res = select![*Maison id, match id 1=>"un" as "a?:String" order_by id](&p.pool).await.unwrap();
@@ -635,6 +995,13 @@ Test! {select_case, async fn func(p:PPool){
     assert_eq!(res[1].a, "lol");
     assert_eq!(res[1].id, 2);
     assert_eq!(res[2].a, "lol");
+
+    // ranges
+    let res = select![*Maison id, match id 1..3=>"small",3..=3=>"medium",_=>"big" as "a!:String" order_by id](&p.pool).await.unwrap();
+    assert_eq!(res[0].a, "small");
+    assert_eq!(res[1].a, "small");
+    assert_eq!(res[2].a, "medium");
+    assert_eq!(res[3].a, "big");
 }}
 
 Test! {select_optional_and_stream, async fn func(p:PPool){
@@ -649,3 +1016,36 @@ Test! {select_optional_and_stream, async fn func(p:PPool){
    let res = select![? Maison where id==99999](&p.pool).await.unwrap();
    assert_eq![res, None];
 }}
+
+Test! {select_order_by_column_enum, async fn func(p:PPool){
+   use crate::{MaisonColumn, PieceFkColumn, WithAttrsColumn};
+   // from query string
+   let col: MaisonColumn = "taille".parse().unwrap();
+   let res = select![*Maison order_by -::col](&p.pool).await.unwrap();
+   assert_eq!(res.iter().map(|m| m.id).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+   let col = MaisonColumn::Id;
+   let res = select![*Maison where id > 1 order_by ::col](&p.pool).await.unwrap();
+   assert_eq!(res.iter().map(|m| m.id).collect::<Vec<_>>(), vec![2, 3, 4]);
+   // column attribute: field and column names are both accepted
+   assert_eq!("lglg".parse::<WithAttrsColumn>().unwrap(), WithAttrsColumn::Lglg);
+   let col: WithAttrsColumn = "lg".parse().unwrap();
+   assert_eq!(col.column(), "lg");
+   assert_eq!(col.to_string(), "lglg");
+   let res = select![. WithAttrs order_by -::col](&p.pool).await.unwrap();
+   assert_eq!(res.lglg, 9);
+   // with joins, column is qualified
+   let col = PieceFkColumn::La;
+   let res = select![*PieceFk where maison.taille > 102 order_by ::col, lg](&p.pool).await.unwrap();
+   assert_eq!(res.iter().map(|p| p.la).collect::<Vec<_>>(), vec![40, 70]);
+   // unknown column
+   assert_eq!(
+       "nope".parse::<MaisonColumn>(),
+       Err(sqlo::Error::UnknownColumn("nope".to_string()))
+   );
+   assert_eq!(MaisonColumn::ALL.len(), 4);
+   // stream
+   use futures_lite::stream::StreamExt;
+   let col = MaisonColumn::Taille;
+   let mut stream = select![+ Maison order_by -::col](&p.pool);
+   assert_eq!(stream.try_next().await.unwrap().unwrap().id, 4);
+}}
@@ -1,4 +1,4 @@
-use crate::{Adresse, Maison, PPool, PieceFk, WithAttrs};
+use crate::{Adresse, Maison, MaisonLit, PPool, PieceFk, WithAttrs};
 use sqlo::{select, update};
 
 Test! {update_all_rows, async fn func(p: PPool) {
@@ -189,3 +189,33 @@ Test! {update_with_assigment_without_dotdot, async fn func(p: PPool) {
     let res = select![.Maison where id ==1](&p.pool).await.unwrap();
     assert_eq!(res.taille, 1); // column is used
 }}
+
+Test! {update_composite_pk, async fn func(p: PPool) {
+    // brackets
+    update![MaisonLit[(1, 2)] quantite = 9](&p.pool).await.unwrap();
+    assert_eq!(MaisonLit::get(&p.pool, (1, 2)).await.unwrap().quantite, 9);
+    assert_eq!(MaisonLit::get(&p.pool, (1, 1)).await.unwrap().quantite, 2);
+    // instance
+    let ml = MaisonLit::get(&p.pool, (2, 3)).await.unwrap();
+    update![MaisonLit(ml) quantite = 10](&p.pool).await.unwrap();
+    assert_eq!(MaisonLit::get(&p.pool, (2, 3)).await.unwrap().quantite, 10);
+    assert_eq!(MaisonLit::get(&p.pool, (1, 2)).await.unwrap().quantite, 9);
+}}
+
+Test! {update_where_optional, async fn func(p: PPool) {
+    let mut min_lg = Some(5);
+    update![PieceFk la = 0 where lg > ?::min_lg](&p.pool).await.unwrap();
+    assert_eq!(PieceFk::get(&p.pool, &uu4!(6)).await.unwrap().la, 0);
+    assert_eq!(PieceFk::get(&p.pool, &uu4!(5)).await.unwrap().la, 50);
+    min_lg = None;
+    update![PieceFk la = 1 where lg > ?::min_lg](&p.pool).await.unwrap();
+    assert_eq!(PieceFk::get(&p.pool, &uu4!(5)).await.unwrap().la, 1);
+}}
+
+Test! {update_where_in_runtime_list, async fn func(p: PPool) {
+    let ids = vec![1, 2];
+    update![Maison taille = 0 where id in ::ids](&p.pool).await.unwrap();
+    assert_eq!(Maison::get(&p.pool, 1).await.unwrap().taille, 0);
+    assert_eq!(Maison::get(&p.pool, 2).await.unwrap().taille, 0);
+    assert_eq!(Maison::get(&p.pool, 3).await.unwrap().taille, 103);
+}}
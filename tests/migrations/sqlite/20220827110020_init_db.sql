@@ -88,3 +88,15 @@ INSERT INTO self_relation VALUES
   (1, 'axel', 12, 3),
   (2, 'margaux', 10, 1),
   (3, 'papa', 1200, NULL);
+
+CREATE TABLE IF NOT EXISTS maison_lit (
+    maison_id INTEGER NOT NULL,
+    lit_id INTEGER NOT NULL,
+    quantite INTEGER NOT NULL,
+  PRIMARY KEY (maison_id, lit_id)
+    );
+
+INSERT INTO maison_lit VALUES
+  (1, 1, 2),
+  (1, 2, 1),
+  (2, 3, 4);